@@ -1,5 +1,5 @@
 use std::convert::TryInto;
-use syn::{parse::Parse, parse_macro_input, Token};
+use syn::{parse::Parse, parse_macro_input, spanned::Spanned, Token};
 
 #[derive(Debug)]
 enum MemoryRegionAttribute {
@@ -23,8 +23,17 @@ impl Parse for MemoryRegionAttribute {
             }
             Ok(ident) if ident == "access" => {
                 let _: Token![=] = input.parse()?;
-                let name = input.parse()?;
-                Ok(Self::Access(name))
+                let access: syn::LitStr = input.parse()?;
+                match access.value().as_str() {
+                    "RX" | "RW" | "RWX" => Ok(Self::Access(access)),
+                    value => {
+                        let message = format!(
+                            "Unknown access type `{}`, expected one of \"RX\", \"RW\" or \"RWX\"",
+                            value
+                        );
+                        Err(syn::Error::new(access.span(), message))
+                    }
+                }
             }
             Ok(ident) => {
                 let message = format!("Unknown memory region attribute with name `{}`", ident);
@@ -62,6 +71,10 @@ impl Parse for MemoryRegion {
 enum SectionAttribute {
     Region(syn::Ident),
     Offset(syn::LitInt),
+    Align(syn::LitInt),
+    Paint(syn::LitBool),
+    Name(syn::LitStr),
+    Guard(syn::Expr),
     Size(syn::Expr),
     Vma(syn::Ident),
     Lma(syn::Ident),
@@ -80,6 +93,30 @@ impl Parse for SectionAttribute {
                 let name = input.parse()?;
                 Ok(Self::Offset(name))
             }
+            Ok(ident) if ident == "align" => {
+                let _: Token![=] = input.parse()?;
+                let align: syn::LitInt = input.parse()?;
+                if !align.base10_parse::<u64>()?.is_power_of_two() {
+                    let message = format!("Alignment `{}` is not a power of two", align);
+                    return Err(syn::Error::new(align.span(), message));
+                }
+                Ok(Self::Align(align))
+            }
+            Ok(ident) if ident == "paint" => {
+                let _: Token![=] = input.parse()?;
+                let paint = input.parse()?;
+                Ok(Self::Paint(paint))
+            }
+            Ok(ident) if ident == "name" => {
+                let _: Token![=] = input.parse()?;
+                let name = input.parse()?;
+                Ok(Self::Name(name))
+            }
+            Ok(ident) if ident == "guard" => {
+                let _: Token![=] = input.parse()?;
+                let guard = input.parse()?;
+                Ok(Self::Guard(guard))
+            }
             Ok(ident) if ident == "size" => {
                 let _: Token![=] = input.parse()?;
                 let name = input.parse()?;
@@ -124,6 +161,9 @@ impl Parse for Section {
             ident if ident == "CcramBss" => ident,
             ident if ident == "VectorTable" => ident,
             ident if ident == "Ramfunc" => ident,
+            ident if ident == "Heap" => ident,
+            ident if ident == "Stack" => ident,
+            ident if ident == "Uninit" => ident,
             ident => {
                 let message = format!("{} is not a valid `Section` identifier", ident);
                 return Err(syn::Error::new(ident.span(), message));
@@ -154,7 +194,21 @@ impl Parse for MemoryRegions {
         let content;
         let _ = syn::braced!(content in input);
 
-        let regions = content.parse_terminated(MemoryRegion::parse)?;
+        let regions: syn::punctuated::Punctuated<MemoryRegion, Token![,]> =
+            content.parse_terminated(MemoryRegion::parse)?;
+
+        // Region names end up lowercased in the linker script, where they must be unique
+        let mut names = std::collections::HashSet::new();
+        for region in &regions {
+            if !names.insert(region.name.to_string().to_lowercase()) {
+                let message = format!(
+                    "Duplicate memory region `{}`, region names are case-insensitive",
+                    region.name
+                );
+                return Err(syn::Error::new(region.name.span(), message));
+            }
+        }
+
         Ok(MemoryRegions { ident, regions })
     }
 }
@@ -301,8 +355,20 @@ impl TryInto<proc_macro::TokenStream> for LinkerScript {
                     _ => None,
                 })
                 .unwrap();
+            let access = region.attributes.iter().find_map(|attr| match attr {
+                MemoryRegionAttribute::Access(x) => Some(x),
+                _ => None,
+            });
+
+            // Regions without an explicit access type keep the most permissive one
+            let method = match access.map(|access| access.value()) {
+                Some(access) if access == "RX" => quote::quote! { add_rx_region },
+                Some(access) if access == "RW" => quote::quote! { add_rw_region },
+                _ => quote::quote! { add_rwx_region },
+            };
+
             quote::quote! {
-                let #name = layout.add_rwx_region(#lowercase_name, ::ld_script::Address::new(#address), #size)?;
+                let #name = layout.#method(#lowercase_name, ::ld_script::Address::new(#address), #size)?;
             }
         });
 
@@ -325,33 +391,162 @@ impl TryInto<proc_macro::TokenStream> for LinkerScript {
                 SectionAttribute::Size(x) => Some(x),
                 _ => None,
             });
+            let offset = section.attributes.iter().find_map(|attr| match attr {
+                SectionAttribute::Offset(x) => Some(x),
+                _ => None,
+            });
+            let align = section.attributes.iter().find_map(|attr| match attr {
+                SectionAttribute::Align(x) => Some(x),
+                _ => None,
+            });
+            let paint = section.attributes.iter().find_map(|attr| match attr {
+                SectionAttribute::Paint(x) => Some(x),
+                _ => None,
+            });
+            if let Some(paint) = paint.filter(|_| name != "Stack") {
+                return syn::Error::new(paint.span(), "Only a `Stack` can be painted")
+                    .to_compile_error();
+            }
+            let stack_name = section.attributes.iter().find_map(|attr| match attr {
+                SectionAttribute::Name(x) => Some(x),
+                _ => None,
+            });
+            if let Some(stack_name) = stack_name.filter(|_| name != "Stack") {
+                return syn::Error::new(stack_name.span(), "Only a `Stack` can be named")
+                    .to_compile_error();
+            }
+            let guard = section.attributes.iter().find_map(|attr| match attr {
+                SectionAttribute::Guard(x) => Some(x),
+                _ => None,
+            });
+            if let Some(guard) = guard.filter(|_| name != "Stack") {
+                return syn::Error::new(guard.span(), "Only a `Stack` can have a guard")
+                    .to_compile_error();
+            }
 
-            match (vma, lma, region, size) {
-                (Some(vma), Some(lma), None, Some(size)) => {
-                    quote::quote! {
-                        layout.custom_section(#lowercase_name, &#vma, &#lma, Some(#size))?;
-                    }
+            let (vma, lma) = match (vma, lma, region) {
+                (Some(vma), Some(lma), None) => (vma, lma),
+                (None, None, Some(region)) => (region, region),
+                _ => {
+                    return syn::Error::new(
+                        name.span(),
+                        "Section should have either (Vma, Lma) or Region",
+                    )
+                    .to_compile_error()
                 }
-                (None, None, Some(region), Some(size)) => {
-                    quote::quote! {
-                        layout.custom_section(#lowercase_name, &#region, &#region, Some(#size))?;
-                    }
+            };
+            let size = match size {
+                Some(size) => quote::quote! { Some(#size) },
+                None => quote::quote! { None },
+            };
+
+            // Dispatch to the dedicated method of each section, so that the access type of the
+            // regions it is placed in is checked
+            let (section_name, add_section) = match name.to_string().as_str() {
+                "VectorTable" => (
+                    "vector_table".to_owned(),
+                    quote::quote_spanned! {name.span()=>
+                        layout.vector_table(&#vma, &#lma, #size)?;
+                    },
+                ),
+                "Text" => (
+                    "text".to_owned(),
+                    quote::quote_spanned! {name.span()=>
+                        layout.text(&#vma, &#lma, #size)?;
+                    },
+                ),
+                "Ramfunc" => (
+                    "ramfunc".to_owned(),
+                    quote::quote_spanned! {name.span()=>
+                        layout.ramfunc(&#vma, &#lma, #size)?;
+                    },
+                ),
+                "Data" => (
+                    "data".to_owned(),
+                    quote::quote_spanned! {name.span()=>
+                        layout.data(&#vma, &#lma, #size)?;
+                    },
+                ),
+                "Bss" => (
+                    "bss".to_owned(),
+                    quote::quote_spanned! {name.span()=>
+                        layout.bss(&#vma, #size)?;
+                    },
+                ),
+                "Heap" => (
+                    "heap".to_owned(),
+                    quote::quote_spanned! {name.span()=>
+                        layout.heap(&#vma, #size)?;
+                    },
+                ),
+                "CcramData" => (
+                    lowercase_name.clone(),
+                    quote::quote_spanned! {name.span()=>
+                        layout.named_data(#lowercase_name, &#vma, &#lma, #size)?;
+                    },
+                ),
+                "CcramBss" => (
+                    lowercase_name.clone(),
+                    quote::quote_spanned! {name.span()=>
+                        layout.named_bss(#lowercase_name, &#vma, #size)?;
+                    },
+                ),
+                "Uninit" => (
+                    "uninit".to_owned(),
+                    quote::quote_spanned! {name.span()=>
+                        layout.uninit(&#vma, #size)?;
+                    },
+                ),
+                // Additional stacks, e.g. for another core, are told apart by their name
+                "Stack" => {
+                    let stack_name = stack_name
+                        .map(|stack_name| stack_name.value())
+                        .unwrap_or_else(|| "stack".to_owned());
+                    (
+                        stack_name.clone(),
+                        quote::quote_spanned! {name.span()=>
+                            layout.named_stack(#stack_name, &#vma, #size)?;
+                        },
+                    )
                 }
-                (Some(vma), Some(lma), None, None) => {
-                    quote::quote! {
-                        layout.custom_section(#lowercase_name, &#vma, &#lma, None)?;
-                    }
+                _ => (
+                    lowercase_name.clone(),
+                    quote::quote_spanned! {name.span()=>
+                        layout.custom_section(#lowercase_name, &#vma, &#lma, #size)?;
+                    },
+                ),
+            };
+
+            let set_offset = offset.map(|offset| {
+                quote::quote! {
+                    layout.set_section_offset(#section_name, ::ld_script::U32Ext::bytes(#offset))?;
                 }
-                (None, None, Some(region), None) => {
-                    quote::quote! {
-                        layout.custom_section(#lowercase_name, &#region, &#region, None)?;
-                    }
+            });
+
+            let set_alignment = align.map(|align| {
+                quote::quote! {
+                    layout.set_section_alignment(#section_name, ::ld_script::U32Ext::bytes(#align))?;
                 }
-                _ => syn::Error::new(
-                    name.span(),
-                    "Section should have either (Vma, Lma) or Region",
-                )
-                .to_compile_error(),
+            });
+
+            let set_guard = guard.map(|guard| {
+                quote::quote! {
+                    layout.set_stack_guard(#section_name, #guard)?;
+                }
+            });
+
+            let set_painting = paint.map(|paint| {
+                quote::quote! {
+                    layout.set_stack_painting(#section_name, #paint)?;
+                }
+            });
+
+            quote::quote! {
+                #add_section
+                #set_offset
+                #set_alignment
+                #set_guard
+                #set_painting
             }
         });
 
@@ -366,15 +561,21 @@ impl TryInto<proc_macro::TokenStream> for LinkerScript {
                     }
                 }
 
-                fn generate(&self) -> Result<(), ::ld_script::Error> {
+                // Memory regions are bound to variables named after the user identifiers
+                #[allow(non_snake_case, unused_variables)]
+                fn layout(&self) -> Result<::ld_script::MemoryLayout, ::ld_script::Error> {
                     let mut layout = ::ld_script::MemoryLayout::new().unwrap();
                     #(#memory_regions)*
                     #(#sections)*
-                    layout.generate(&self.output_dir)
+                    Ok(layout)
+                }
+
+                fn generate(&self) -> Result<(), ::ld_script::Error> {
+                    self.layout()?.generate(&self.output_dir)
                 }
 
                 fn generate_reset(&self) -> Result<(), ::ld_script::Error> {
-                    Ok(())
+                    self.layout()?.generate_reset(&self.output_dir)
                 }
             }
         };
@@ -391,3 +592,35 @@ pub fn define_linker_script(item: proc_macro::TokenStream) -> proc_macro::TokenS
         Err(error) => error.to_compile_error().into(),
     }
 }
+
+/// Places a function in its own `.ramfunc.<name>` input section, so that it runs from the RAM the
+/// `.ramfunc` section of the layout is placed in, e.g. to keep running while the flash is being
+/// programmed. The function is never inlined, as it would end up in the flash of its caller.
+///
+/// Calls between flash and RAM are usually out of the range of a direct branch, e.g. the
+/// ±16 MiB of a Thumb `BL`. No `long_call` is needed for them, as the linker routes such calls
+/// through veneers it places next to the caller, i.e. in `.ramfunc` for calls from a ramfunc.
+/// Code called by a ramfunc that is not inlined, including its closures, still runs from flash.
+#[proc_macro_attribute]
+pub fn ramfunc(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    if !attr.is_empty() {
+        let attr = proc_macro2::TokenStream::from(attr);
+        return syn::Error::new_spanned(attr, "`#[ramfunc]` takes no arguments")
+            .to_compile_error()
+            .into();
+    }
+
+    let function = parse_macro_input!(item as syn::ItemFn);
+    let section = format!(".ramfunc.{}", function.sig.ident);
+
+    let code = quote::quote! {
+        #[link_section = #section]
+        #[inline(never)]
+        #function
+    };
+
+    code.into()
+}
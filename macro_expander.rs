@@ -28,7 +28,7 @@ define_linker_script! {
         VectorTable => {
             region = Flash,
             offset = 0x00,
-            size = 128.kilobytes(),
+            size = 1.kilobytes(),
         },
 
         Text => {
@@ -49,9 +49,9 @@ define_linker_script! {
         },
 
         CcramData => {
-            vma = Ram,
+            vma = CcRam,
             lma = Flash,
-            size = 32.kilobytes(),
+            size = 8.kilobytes(),
         },
 
         Bss => {
@@ -60,8 +60,8 @@ define_linker_script! {
         },
 
         CcramBss => {
-            region = Ram,
-            size = 32.kilobytes(),
+            region = CcRam,
+            size = 8.kilobytes(),
         },
     },
 }
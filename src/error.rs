@@ -0,0 +1,231 @@
+use std::fmt;
+use std::path::PathBuf;
+
+use crate::{Address, MemoryId, Size};
+
+/// Error type for the ld_script crate.
+#[derive(Debug)]
+pub enum Error {
+    /// The new region overlaps a region that was already in the layout.
+    OverlapingMemoryRegion {
+        region: MemoryId,
+        base_address: Address,
+        size: Size,
+        existing: MemoryId,
+        existing_base_address: Address,
+        existing_size: Size,
+    },
+    /// The region extends past the end of the address space.
+    AddressWrapAround(MemoryId),
+    ZeroSizedRegion(MemoryId),
+    /// A region with the same name already exists. Names are compared case-insensitively.
+    DuplicateRegion(MemoryId),
+    /// A section is placed in a region that is not part of the layout.
+    UnknownRegion {
+        section: String,
+        region: MemoryId,
+    },
+    /// The size budgets of the sections placed in a region exceed its capacity.
+    RegionOverflow {
+        region: MemoryId,
+        requested: Size,
+        available: Size,
+    },
+    /// The region a section runs from lacks an access type the section needs.
+    PermissionMismatch {
+        section: String,
+        region: MemoryId,
+        required: &'static str,
+        available: &'static str,
+    },
+    /// Two sections placed at fixed offsets of the same region overlap.
+    OverlappingSections(String, String),
+    UnknownSection(String),
+    /// A section alignment that is not a power of two.
+    InvalidAlignment {
+        section: String,
+        alignment: Size,
+    },
+    /// A section at a fixed offset starts at an address that does not have its alignment.
+    MisalignedSection {
+        section: String,
+        address: Address,
+        alignment: Size,
+    },
+    /// A section without a size budget follows a heap that fills the rest of its region.
+    UnsizedSectionAfterHeap {
+        section: String,
+        region: MemoryId,
+    },
+    /// A stack has no size budget, although it is at the bottom of its region or shares its
+    /// region with other stacks.
+    UnsizedStack {
+        section: String,
+        region: MemoryId,
+    },
+    /// A stack guard is set on a section that is not a stack.
+    NotAStack(String),
+    /// `.text` runs from another region than the one it is loaded from, although the reset code
+    /// that would copy it is part of it.
+    CopiedText {
+        vma: MemoryId,
+        lma: MemoryId,
+    },
+    /// A string could not be parsed as a [`Size`].
+    InvalidSize(String),
+    /// A generated file could not be written.
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+}
+
+fn range(base_address: Address, size: Size) -> String {
+    // The end of a region can be right past the top of the address space
+    let end = u128::from(base_address.0) + u128::from(size.0);
+    format!("{}..{:#010x}", base_address, end)
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::OverlapingMemoryRegion {
+                region,
+                base_address,
+                size,
+                existing,
+                existing_base_address,
+                existing_size,
+            } => write!(
+                f,
+                "memory region `{}` ({}) overlaps with memory region `{}` ({})",
+                region,
+                range(*base_address, *size),
+                existing,
+                range(*existing_base_address, *existing_size)
+            ),
+            Error::AddressWrapAround(region) => write!(
+                f,
+                "memory region `{}` extends past the end of the address space",
+                region
+            ),
+            Error::ZeroSizedRegion(region) => {
+                write!(f, "memory region `{}` has a size of zero", region)
+            }
+            Error::DuplicateRegion(region) => {
+                write!(f, "a memory region named `{}` already exists", region)
+            }
+            Error::UnknownRegion { section, region } => write!(
+                f,
+                "section `.{}` is placed in memory region `{}`, which is not part of the layout",
+                section, region
+            ),
+            Error::RegionOverflow {
+                region,
+                requested,
+                available,
+            } => write!(
+                f,
+                "sections in memory region `{}` need {}, but only {} are available",
+                region, requested, available
+            ),
+            Error::PermissionMismatch {
+                section,
+                region,
+                required,
+                available,
+            } => write!(
+                f,
+                "section `.{}` needs `{}` access, but memory region `{}` is `{}`",
+                section, required, region, available
+            ),
+            Error::OverlappingSections(first, second) => write!(
+                f,
+                "sections `.{}` and `.{}` overlap at their fixed offsets",
+                first, second
+            ),
+            Error::UnknownSection(section) => {
+                write!(f, "there is no section named `.{}` in the layout", section)
+            }
+            Error::InvalidAlignment { section, alignment } => write!(
+                f,
+                "alignment of section `.{}` must be a power of two, but it is {}",
+                section, alignment
+            ),
+            Error::MisalignedSection {
+                section,
+                address,
+                alignment,
+            } => write!(
+                f,
+                "section `.{}` starts at {}, which is not aligned to {}",
+                section, address, alignment
+            ),
+            Error::UnsizedSectionAfterHeap { section, region } => write!(
+                f,
+                "section `.{}` needs a size budget, as it follows a heap that fills the rest of \
+                 memory region `{}`",
+                section, region
+            ),
+            Error::UnsizedStack { section, region } => write!(
+                f,
+                "stack `.{}` needs a size budget, as it is at the bottom of memory region `{}` or \
+                 shares it with other stacks",
+                section, region
+            ),
+            Error::NotAStack(section) => write!(f, "section `.{}` is not a stack", section),
+            Error::CopiedText { vma, lma } => write!(
+                f,
+                "section `.text` cannot run from memory region `{}` while it is loaded from \
+                 memory region `{}`, as the reset code that would copy it is part of it",
+                vma, lma
+            ),
+            Error::InvalidSize(input) => write!(f, "`{}` is not a valid size", input),
+            Error::Io { path, source } => {
+                write!(f, "failed to write `{}`: {}", path.display(), source)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Error;
+    use crate::{Address, MemoryId, Size};
+
+    #[test]
+    fn overlap_message_shows_both_ranges() {
+        let error = Error::OverlapingMemoryRegion {
+            region: MemoryId("sram".to_owned()),
+            base_address: Address(0x20008000),
+            size: Size(0x10000),
+            existing: MemoryId("ram".to_owned()),
+            existing_base_address: Address(0x20000000),
+            existing_size: Size(0x10000),
+        };
+        assert_eq!(
+            error.to_string(),
+            "memory region `sram` (0x20008000..0x20018000) overlaps with memory region `ram` \
+             (0x20000000..0x20010000)"
+        );
+    }
+
+    #[test]
+    fn io_error_has_source() {
+        let error = Error::Io {
+            path: "out/link.x".into(),
+            source: std::io::Error::new(std::io::ErrorKind::NotFound, "not found"),
+        };
+        assert_eq!(error.to_string(), "failed to write `out/link.x`: not found");
+        assert!(std::error::Error::source(&error).is_some());
+    }
+}
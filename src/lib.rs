@@ -1,48 +1,23 @@
-use std::collections::hash_map::HashMap;
+use std::fmt::Write as _;
 use std::marker::PhantomData;
 
-#[cfg(feature = "macros")]
-pub use ld_script_macros::define_linker_script;
-
-/// Error type for the ld_script crate.
-#[derive(Debug)]
-pub enum Error {
-    OverlapingMemoryRegion(MemoryId),
-}
-
-#[derive(Copy, Clone, Debug)]
-pub struct Address(u32);
-
-impl Address {
-    pub fn new(addr: u32) -> Self {
-        Self(addr)
-    }
-}
+mod error;
+mod units;
 
-#[derive(Copy, Clone, Debug)]
-pub struct Size(u32);
+pub use error::Error;
+#[cfg(feature = "macros")]
+pub use ld_script_macros::{define_linker_script, ramfunc};
+pub use units::{Address, AddressWidth, Size, U32Ext};
 
-pub trait U32Ext {
-    fn bytes(self) -> Size;
-    fn kilobytes(self) -> Size;
-    fn megabytes(self) -> Size;
-}
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct MemoryId(String);
 
-impl U32Ext for u32 {
-    fn bytes(self) -> Size {
-        Size(self)
-    }
-    fn kilobytes(self) -> Size {
-        Size(self * 1024)
-    }
-    fn megabytes(self) -> Size {
-        Size(self * 1024 * 1024)
+impl std::fmt::Display for MemoryId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
     }
 }
 
-#[derive(Clone, Debug)]
-pub struct MemoryId(String);
-
 #[derive(Debug)]
 pub struct RW {}
 
@@ -52,9 +27,26 @@ pub struct RX {}
 #[derive(Debug)]
 pub struct RWX {}
 
-pub trait Read {}
-pub trait Write {}
-pub trait Execute {}
+/// Access type of a memory region, as expressed in the `MEMORY` command of the linker script.
+pub trait Access {
+    const ATTRIBUTES: &'static str;
+}
+
+impl Access for RW {
+    const ATTRIBUTES: &'static str = "rw";
+}
+
+impl Access for RX {
+    const ATTRIBUTES: &'static str = "rx";
+}
+
+impl Access for RWX {
+    const ATTRIBUTES: &'static str = "rwx";
+}
+
+pub trait Read: Access {}
+pub trait Write: Access {}
+pub trait Execute: Access {}
 
 impl Read for RW {}
 impl Write for RW {}
@@ -70,6 +62,7 @@ pub trait MemoryRegion {
     fn get_id(&self) -> &MemoryId;
     fn get_base_addres(&self) -> Address;
     fn get_size(&self) -> Size;
+    fn get_attributes(&self) -> &'static str;
 }
 
 /// A representation of a memory region with read, write and execute permissions.
@@ -82,7 +75,7 @@ pub struct Memory<Type> {
 }
 
 // Implementation for a read write memory
-impl<T> MemoryRegion for Memory<T> {
+impl<T: Access> MemoryRegion for Memory<T> {
     fn get_id(&self) -> &MemoryId {
         &self.name
     }
@@ -94,17 +87,545 @@ impl<T> MemoryRegion for Memory<T> {
     fn get_size(&self) -> Size {
         self.size
     }
+
+    fn get_attributes(&self) -> &'static str {
+        T::ATTRIBUTES
+    }
+}
+
+// Symbols exported by the linker script for each section. The reset code refers to them by name,
+// so both must be generated from these helpers.
+fn start_symbol(section: &str) -> String {
+    format!("__s{}", section)
+}
+
+fn end_symbol(section: &str) -> String {
+    format!("__e{}", section)
+}
+
+fn load_symbol(section: &str) -> String {
+    format!("__si{}", section)
+}
+
+// Tables of the sections the reset code initializes, see `MemoryLayout::render_tables`
+const COPY_TABLE: &str = "copy_table";
+const ZERO_TABLE: &str = "zero_table";
+
+/// Initial stack pointer of a stack, i.e. the top of the stack.
+fn stack_start_symbol(stack: &str) -> String {
+    format!("_{}_start", stack)
+}
+
+// Fixed parts of the generated reset code. The linker symbol declarations go right after the
+// prologue, and the `copy` and `zero` helpers, if any, and the loops over the tables right after
+// the helpers. The code compiles on every edition, unsafe operations being in `unsafe` blocks even
+// in unsafe functions.
+const RESET_PROLOGUE: &str = r#"// Generated by ld_script. Do not edit.
+
+/// Entry point of the firmware. Initializes memory and calls `main`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn Reset() -> ! {
+    unsafe extern "C" {
+"#;
+
+const RESET_HELPERS: &str = r#"    }
+
+    unsafe extern "Rust" {
+        fn main() -> !;
+    }
+
+"#;
+
+const COPY_HELPER: &str = r#"    unsafe fn copy(mut src: *const u32, mut dst: *mut u32, end: *mut u32) {
+        while dst < end {
+            unsafe {
+                ::core::ptr::write_volatile(dst, ::core::ptr::read(src));
+                dst = dst.add(1);
+                src = src.add(1);
+            }
+        }
+    }
+
+"#;
+
+const ZERO_HELPER: &str = r#"    unsafe fn zero(mut dst: *mut u32, end: *mut u32) {
+        while dst < end {
+            unsafe {
+                ::core::ptr::write_volatile(dst, 0);
+                dst = dst.add(1);
+            }
+        }
+    }
+
+"#;
+
+// Stack painting. The stacks are painted by an assembly loop, as a function called while painting,
+// e.g. `write_volatile` in a debug build, would push its frame below the stack pointer and have it
+// painted over. The calls to `paint!` go right after the prologue.
+const PAINT_PROLOGUE: &str = r#"
+    let sp: *mut u32;
+    unsafe { ::core::arch::asm!("mov {}, sp", out(reg) sp) };
+
+    macro_rules! paint {
+        ($start:expr, $end:expr) => {
+            let start: *mut u32 = $start;
+            let end: *mut u32 = $end;
+            // The stack in use is only painted up to the stack pointer
+            let end = if start <= sp && sp < end { sp } else { end };
+            unsafe {
+                ::core::arch::asm!(
+                    "2:",
+                    "cmp {word}, {end}",
+                    "bhs 3f",
+                    "str {paint}, [{word}]",
+                    "adds {word}, #4",
+                    "b 2b",
+                    "3:",
+                    word = inout(reg) start => _,
+                    end = in(reg) end,
+                    paint = in(reg) STACK_PAINT,
+                    options(nostack),
+                )
+            };
+        };
+    }
+
+"#;
+
+const RESET_EPILOGUE: &str = r#"    unsafe { main() }
+}
+"#;
+
+// Measurement of the stack usage. A `<stack>_high_water_mark` function for each stack goes right
+// after these helpers.
+const HIGH_WATER_MARK_HELPERS: &str = r#"
+/// The word the stacks are painted with by `Reset`.
+pub const STACK_PAINT: u32 = 0xdeadbeef;
+
+/// The most stack space used since reset in bytes, found by scanning the stack from the bottom
+/// for the first word that is no longer painted.
+unsafe fn high_water_mark(start: *const u32, end: *const u32) -> usize {
+    let mut word = start;
+    while word < end && unsafe { ::core::ptr::read_volatile(word) } == STACK_PAINT {
+        word = unsafe { word.add(1) };
+    }
+    end as usize - word as usize
+}
+"#;
+
+// Helper that hands the heap over to an allocator. The declarations of the heap symbols and the
+// pointers to them go between the prologue and the epilogue.
+const HEAP_PROLOGUE: &str = r#"
+/// The memory the linker script reserves for the heap, e.g. to initialize an allocator with.
+///
+/// # Safety
+///
+/// Must be called at most once, as every call returns the same memory.
+pub unsafe fn heap() -> &'static mut [::core::mem::MaybeUninit<u8>] {
+    unsafe extern "C" {
+"#;
+
+const HEAP_EPILOGUE: &str = r#"    unsafe { ::core::slice::from_raw_parts_mut(start.cast(), end as usize - start as usize) }
+}
+"#;
+
+// The header at the start of `.uninit`: a magic word and the CRC-32 of the rest of the section.
+const UNINIT_HEADER: Size = Size(8);
+
+// Accessor to the contents of `.uninit` and the check of their header. The declarations of the
+// `.uninit` symbols and the pointers to them go between the prologue and the epilogue.
+const UNINIT_PROLOGUE: &str = r#"
+/// The magic word in the header of `.uninit`, written by `seal_uninit`.
+pub const UNINIT_MAGIC: u32 = 0x494e4f4e;
+
+// The header of `.uninit`, i.e. `UNINIT_MAGIC` and the CRC-32 of the contents, and its contents
+unsafe fn uninit_memory() -> (*mut u32, *mut u8, usize) {
+    unsafe extern "C" {
+"#;
+
+const UNINIT_EPILOGUE: &str = r#"    let contents = unsafe { start.add(2) }.cast::<u8>();
+    (start, contents, end as usize - contents as usize)
+}
+
+unsafe fn uninit_crc(contents: *const u8, len: usize) -> u32 {
+    let mut crc = !0u32;
+    for i in 0..len {
+        crc ^= u32::from(unsafe { ::core::ptr::read_volatile(contents.add(i)) });
+        for _ in 0..8 {
+            crc = (crc >> 1) ^ (0xedb88320 & (crc & 1).wrapping_neg());
+        }
+    }
+    !crc
+}
+
+/// The contents of `.uninit`, which `Reset` neither copies nor zeroes. They are `Ok` if they
+/// match the header written by `seal_uninit` before a warm reset, and `Err` after a cold boot, when
+/// they hold whatever the RAM powered up with.
+///
+/// # Safety
+///
+/// Must not be called while the memory returned by an earlier call is still in use.
+pub unsafe fn uninit() -> Result<&'static mut [u8], &'static mut [::core::mem::MaybeUninit<u8>]> {
+    unsafe {
+        let (header, contents, len) = uninit_memory();
+        if ::core::ptr::read_volatile(header) == UNINIT_MAGIC
+            && ::core::ptr::read_volatile(header.add(1)) == uninit_crc(contents, len)
+        {
+            Ok(::core::slice::from_raw_parts_mut(contents, len))
+        } else {
+            Err(::core::slice::from_raw_parts_mut(contents.cast(), len))
+        }
+    }
+}
+
+/// Updates the header of `.uninit` to match its contents, so that they are `Ok` after the next
+/// warm reset. Must be called after every change to them.
+///
+/// # Safety
+///
+/// Must not be called while the memory returned by `uninit` is being written.
+pub unsafe fn seal_uninit() {
+    unsafe {
+        let (header, contents, len) = uninit_memory();
+        ::core::ptr::write_volatile(header.add(1), uninit_crc(contents, len));
+        ::core::ptr::write_volatile(header, UNINIT_MAGIC);
+    }
 }
+"#;
 
-struct Section {
+fn write_file(path: &std::path::Path, contents: &str) -> Result<(), Error> {
+    std::fs::write(path, contents).map_err(|source| Error::Io {
+        path: path.to_owned(),
+        source,
+    })
+}
+
+/// The kind of a section determines which input sections it collects and how it is laid out by
+/// the linker.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum SectionKind {
+    VectorTable,
+    Text,
+    Rodata,
+    Ramfunc,
+    Data,
+    Bss,
+    Uninit,
+    Heap,
+    Stack,
+    Custom,
+}
+
+/// Where the stack is placed in its region. The stack grows down from `_stack_start` in both
+/// cases.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum StackPlacement {
+    /// At the top of the region, after all other sections. A stack overflow runs into the
+    /// statics below it.
+    #[default]
+    Top,
+    /// At the bottom of the region, before all other sections. A stack overflow runs off the
+    /// start of the region and faults instead of corrupting the statics.
+    Bottom,
+}
+
+/// A section of the memory layout, as placed by one of the section methods of `MemoryLayout`.
+pub struct Section {
+    name: String,
+    kind: SectionKind,
+    /// Position of the section in the conventional order. Custom sections placed relative to
+    /// another section share its rank, so that they stay next to it.
+    rank: SectionKind,
     vma: MemoryId,
     lma: MemoryId,
     size: Option<Size>,
+    /// Fixed position of the section, relative to the origin of its VMA region.
+    offset: Option<Size>,
+    /// Alignment of the start and the end of the section, on top of the one its kind needs.
+    alignment: Option<Size>,
+    /// Gap left free below a stack, so that an overflow runs into it instead of the section
+    /// below.
+    guard: Option<Size>,
+    /// Whether the reset code paints a stack, so that its high-water mark can be measured.
+    painted: bool,
 }
 
 impl Section {
-    fn new(vma: MemoryId, lma: MemoryId, size: Option<Size>) -> Self {
-        Section { vma, lma, size }
+    fn new(
+        name: String,
+        kind: SectionKind,
+        vma: MemoryId,
+        lma: MemoryId,
+        size: Option<Size>,
+    ) -> Self {
+        Section {
+            name,
+            kind,
+            rank: kind,
+            vma,
+            lma,
+            size,
+            offset: None,
+            alignment: None,
+            guard: None,
+            painted: false,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Keeps the offset, alignment, guard and painting set on the section this one replaces, as
+    /// they are set after the section is added. A guard and painting only apply to a stack.
+    fn keep_settings(&mut self, previous: &Section) {
+        self.offset = previous.offset;
+        self.alignment = previous.alignment;
+        if self.kind == SectionKind::Stack {
+            self.guard = previous.guard;
+            self.painted = previous.painted;
+        }
+    }
+
+    /// The memory region the section runs from.
+    pub fn vma(&self) -> &MemoryId {
+        &self.vma
+    }
+
+    /// The memory region the section is loaded from.
+    pub fn lma(&self) -> &MemoryId {
+        &self.lma
+    }
+
+    /// The size budget of the section, if any.
+    pub fn size(&self) -> Option<Size> {
+        self.size
+    }
+
+    /// The fixed position of the section relative to the origin of its VMA region, if any.
+    pub fn offset(&self) -> Option<Size> {
+        self.offset
+    }
+
+    /// The alignment of the start of the section.
+    pub fn alignment(&self) -> Size {
+        let required = match self.kind {
+            SectionKind::Heap | SectionKind::Stack => Size(8),
+            _ if self.is_word_aligned() => Size(4),
+            _ => Size(1),
+        };
+        self.alignment
+            .map_or(required, |alignment| alignment.max(required))
+    }
+
+    /// The gap left free below the section if it is a stack, if any.
+    pub fn guard(&self) -> Option<Size> {
+        self.guard
+    }
+
+    /// Whether the section is a stack that the reset code paints.
+    pub fn is_painted(&self) -> bool {
+        self.painted
+    }
+
+    /// The space the section takes up in its region, with its size budget rounded up to its
+    /// alignment. Sections without a size budget are considered empty.
+    fn footprint(&self) -> Size {
+        let alignment = self.alignment().0;
+        let size = self
+            .size
+            .map_or(0, |size| size.0.div_ceil(alignment) * alignment);
+        Size(size + self.guard.map_or(0, |guard| guard.0))
+    }
+
+    /// Sections that are copied or zeroed word by word by the reset code must be word aligned, as
+    /// must `.uninit` for its header.
+    fn is_word_aligned(&self) -> bool {
+        matches!(
+            self.kind,
+            SectionKind::Ramfunc | SectionKind::Data | SectionKind::Bss | SectionKind::Uninit
+        ) || self.is_copied()
+    }
+
+    /// Sections that do not take up space in the image.
+    fn is_noload(&self) -> bool {
+        matches!(
+            self.kind,
+            SectionKind::Bss | SectionKind::Uninit | SectionKind::Heap | SectionKind::Stack
+        )
+    }
+
+    /// Access the section needs on its VMA region, on top of being readable.
+    fn required_access(&self) -> &'static str {
+        match self.kind {
+            SectionKind::Text => "x",
+            SectionKind::Ramfunc => "wx",
+            SectionKind::Data
+            | SectionKind::Bss
+            | SectionKind::Uninit
+            | SectionKind::Heap
+            | SectionKind::Stack => "w",
+            // Sections loaded from another region are written when they are copied
+            _ if self.is_copied() => "w",
+            SectionKind::VectorTable | SectionKind::Rodata | SectionKind::Custom => "",
+        }
+    }
+
+    /// Whether the reset code needs to copy this section from its LMA to its VMA.
+    fn is_copied(&self) -> bool {
+        !self.is_noload() && self.vma != self.lma
+    }
+
+    /// Whether the reset code needs to zero this section. In particular, `.uninit` is neither
+    /// zeroed nor copied, so that its contents survive a warm reset.
+    fn is_zeroed(&self) -> bool {
+        self.kind == SectionKind::Bss
+    }
+
+    /// Renders the section. `reserved` is the space taken up by the sections after it in its
+    /// region, which a heap without a size budget leaves free.
+    fn render(&self, script: &mut String, reserved: Size, stack_placement: StackPlacement) {
+        let name = &self.name;
+        let header_alignment = match self.alignment() {
+            Size(1) => String::new(),
+            alignment => format!(" ALIGN({})", alignment.0),
+        };
+        let noload = if self.is_noload() { " (NOLOAD)" } else { "" };
+
+        // Writing to a String never fails
+        // Sections at fixed offsets and stacks at the top of their region are given their address
+        // on the output section itself, as lld ignores assignments to the location counter between
+        // the sections of a region. A guard goes right below its section.
+        let guard = self.guard.map_or(0, |guard| guard.0);
+        let (guard_address, address) = match (self.offset, self.kind, self.size) {
+            (Some(offset), ..) => {
+                let at = |gap: u64| format!(" ORIGIN({}) + {:#x}", self.vma.0, offset.0 + gap);
+                (at(0), at(guard))
+            }
+            // Stacks at the top of their region end where the sections after them start
+            (None, SectionKind::Stack, Some(size)) if stack_placement == StackPlacement::Top => {
+                let below_top = reserved.0 + size.0 + guard;
+                let at = |gap: u64| {
+                    format!(
+                        " ORIGIN({0}) + LENGTH({0}) - {1:#x}",
+                        self.vma.0,
+                        below_top - gap
+                    )
+                };
+                (at(0), at(guard))
+            }
+            _ => (String::new(), String::new()),
+        };
+        if let Some(guard) = self.guard {
+            let guard_name = format!("{}_guard", name);
+            let _ = writeln!(script, "    .{}{} (NOLOAD) :", guard_name, guard_address);
+            script.push_str("    {\n");
+            let _ = writeln!(script, "        {} = .;", start_symbol(&guard_name));
+            let _ = writeln!(script, "        . += {:#x};", guard.0);
+            let _ = writeln!(script, "        {} = .;", end_symbol(&guard_name));
+            let _ = writeln!(script, "    }} > {}", self.vma.0);
+            script.push('\n');
+        }
+        let _ = writeln!(
+            script,
+            "    .{}{}{} :{}",
+            name, address, noload, header_alignment
+        );
+        script.push_str("    {\n");
+        let _ = writeln!(script, "        {} = .;", start_symbol(name));
+        match self.kind {
+            SectionKind::VectorTable => {
+                script.push_str("        KEEP(*(.vector_table .vector_table.*))\n");
+            }
+            SectionKind::Text => script.push_str("        *(.text .text.*)\n"),
+            SectionKind::Rodata => script.push_str("        *(.rodata .rodata.*)\n"),
+            SectionKind::Ramfunc => script.push_str("        *(.ramfunc .ramfunc.*)\n"),
+            SectionKind::Data => {
+                let _ = writeln!(script, "        *(.{} .{}.*)", name, name);
+            }
+            // Common symbols only go to the main `.bss`
+            SectionKind::Bss if name == "bss" => {
+                script.push_str("        *(.bss .bss.* COMMON)\n");
+            }
+            SectionKind::Bss => {
+                let _ = writeln!(script, "        *(.{} .{}.*)", name, name);
+            }
+            SectionKind::Uninit => {
+                let _ = writeln!(script, "        . += {:#x};", UNINIT_HEADER.0);
+                script.push_str("        *(.uninit .uninit.*)\n");
+            }
+            SectionKind::Heap => match self.size {
+                Some(size) => {
+                    let _ = writeln!(script, "        . += {:#x};", size.0);
+                }
+                None if reserved == Size(0) => {
+                    let _ = writeln!(script, "        . = ORIGIN({0}) + LENGTH({0});", self.vma.0);
+                }
+                None => {
+                    let _ = writeln!(
+                        script,
+                        "        . = ORIGIN({0}) + LENGTH({0}) - {1:#x};",
+                        self.vma.0, reserved.0
+                    );
+                }
+            },
+            SectionKind::Stack => {
+                if let Some(size) = self.size {
+                    let _ = writeln!(script, "        . += {:#x};", size.0);
+                }
+            }
+            SectionKind::Custom => {
+                let _ = writeln!(script, "        *(.{} .{}.*)", name, name);
+            }
+        }
+        // The end is aligned too, so that the size of the section is a multiple of the alignment
+        if self.is_word_aligned() || self.alignment.is_some() {
+            let _ = writeln!(script, "        . = ALIGN({});", self.alignment().0);
+        }
+        let _ = writeln!(script, "        {} = .;", end_symbol(name));
+
+        if self.is_noload() || self.vma == self.lma {
+            let _ = writeln!(script, "    }} > {}", self.vma.0);
+        } else {
+            let _ = writeln!(script, "    }} > {} AT> {}", self.vma.0, self.lma.0);
+            let _ = writeln!(script, "    {} = LOADADDR(.{});", load_symbol(name), name);
+        }
+
+        // The names allocator crates, e.g. `embedded-alloc`, conventionally look for
+        if self.kind == SectionKind::Heap {
+            let _ = writeln!(script, "    PROVIDE(_sheap = {});", start_symbol(name));
+            let _ = writeln!(script, "    PROVIDE(_eheap = {});", end_symbol(name));
+        }
+
+        // Initial stack pointer, e.g. for the first entry of the vector table. A stack without a
+        // size budget at the top of its region grows down from the end of the region.
+        if self.kind == SectionKind::Stack {
+            if stack_placement == StackPlacement::Top && self.size.is_none() {
+                let _ = writeln!(
+                    script,
+                    "    {} = ORIGIN({1}) + LENGTH({1});",
+                    stack_start_symbol(name),
+                    self.vma.0
+                );
+            } else {
+                let _ = writeln!(
+                    script,
+                    "    {} = {};",
+                    stack_start_symbol(name),
+                    end_symbol(name)
+                );
+            }
+        }
+
+        // The budget is also enforced at link time, as the actual size is only known then. GNU ld
+        // does not accept a trailing semicolon after an ASSERT inside of SECTIONS.
+        if let Some(size) = self.size {
+            let _ = writeln!(
+                script,
+                "    ASSERT(SIZEOF(.{}) <= {:#x}, \".{} exceeds its budget of {}\")",
+                name, size.0, name, size
+            );
+        }
     }
 }
 
@@ -112,47 +633,93 @@ impl Section {
 /// generate a link.x script for your embedded device, allowing customization of the placement of
 /// each section and how they are laid out in memory.
 pub struct MemoryLayout {
-    sections: HashMap<String, Section>,
+    sections: Vec<Section>,
     memory_regions: Vec<Box<dyn MemoryRegion>>,
+    address_width: AddressWidth,
+    stack_placement: StackPlacement,
 }
 
-impl<'a> MemoryLayout {
+impl MemoryLayout {
+    /// Creates a layout for a target with a 32-bit address space.
     pub fn new() -> Result<Self, Error> {
+        Self::with_address_width(AddressWidth::Bits32)
+    }
+
+    pub fn with_address_width(address_width: AddressWidth) -> Result<Self, Error> {
         Ok(Self {
-            sections: HashMap::new(),
+            sections: vec![],
             memory_regions: vec![],
+            address_width,
+            stack_placement: StackPlacement::default(),
         })
     }
 
-    fn check_overlap(&self, base_address: Address, size: Size) -> Result<(), Error> {
-        let start_address = base_address.0;
-        let end_address = base_address.0 + size.0;
+    pub fn address_width(&self) -> AddressWidth {
+        self.address_width
+    }
+
+    pub fn set_stack_placement(&mut self, stack_placement: StackPlacement) {
+        self.stack_placement = stack_placement;
+    }
+
+    pub fn stack_placement(&self) -> StackPlacement {
+        self.stack_placement
+    }
+
+    // Regions are half-open intervals [start, end). The end is computed in 128 bits, so that a
+    // region may end right at the top of the address space.
+    fn region_end(&self, base_address: Address, size: Size) -> Option<u128> {
+        Some(u128::from(base_address.0) + u128::from(size.0))
+            .filter(|&end| end <= self.address_width.end())
+    }
+
+    fn check_overlap(&self, name: &str, base_address: Address, size: Size) -> Result<(), Error> {
+        if size.0 == 0 {
+            return Err(Error::ZeroSizedRegion(MemoryId(name.to_string())));
+        }
+        let start = u128::from(base_address.0);
+        let end = self
+            .region_end(base_address, size)
+            .ok_or_else(|| Error::AddressWrapAround(MemoryId(name.to_string())))?;
+
         for region in &self.memory_regions {
-            let region_start = region.get_base_addres().0;
-            let region_end = region.get_base_addres().0 + region.get_size().0;
+            let region_start = u128::from(region.get_base_addres().0);
+            // Regions in the layout have already been checked, so their end is valid
+            let region_end = self
+                .region_end(region.get_base_addres(), region.get_size())
+                .unwrap_or(self.address_width.end());
 
-            if start_address > region_start && start_address < region_end {
-                return Err(Error::OverlapingMemoryRegion(region.get_id().clone()));
-            }
-            if end_address > region_start && end_address < region_end {
-                return Err(Error::OverlapingMemoryRegion(region.get_id().clone()));
-            }
-            if start_address < region_start && end_address > region_end {
-                return Err(Error::OverlapingMemoryRegion(region.get_id().clone()));
+            if start < region_end && region_start < end {
+                return Err(Error::OverlapingMemoryRegion {
+                    region: MemoryId(name.to_string()),
+                    base_address,
+                    size,
+                    existing: region.get_id().clone(),
+                    existing_base_address: region.get_base_addres(),
+                    existing_size: region.get_size(),
+                });
             }
         }
         Ok(())
     }
 
-    fn add_region<T>(
+    fn add_region<T: Access + 'static>(
         &mut self,
         name: &str,
         base_address: Address,
         size: Size,
     ) -> Result<Memory<T>, Error> {
-        self.check_overlap(base_address, size)?;
+        // GNU ld region names are effectively case-insensitive
+        if let Some(region) = self
+            .memory_regions
+            .iter()
+            .find(|region| region.get_id().0.eq_ignore_ascii_case(name))
+        {
+            return Err(Error::DuplicateRegion(region.get_id().clone()));
+        }
+        self.check_overlap(name, base_address, size)?;
 
-        self.memory_regions.push(Box::new(Memory::<RWX> {
+        self.memory_regions.push(Box::new(Memory::<T> {
             name: MemoryId(name.to_string()),
             base_address,
             size,
@@ -194,150 +761,947 @@ impl<'a> MemoryLayout {
         self.add_region(name, base_address, size)
     }
 
-    pub fn vector_table<T: Read, U: Read>(
+    fn add_section(
         &mut self,
-        vma: &Memory<T>,
-        lma: &Memory<U>,
+        name: &str,
+        kind: SectionKind,
+        vma: &MemoryId,
+        lma: &MemoryId,
         size: Option<Size>,
     ) -> Result<(), Error> {
-        self.sections.insert(
-            "vector_table".to_owned(),
-            Section::new(lma.get_id().clone(), vma.get_id().clone(), size),
-        );
+        let mut section = Section::new(name.to_owned(), kind, vma.clone(), lma.clone(), size);
+        if let Some(previous) = self.remove_section(name) {
+            section.keep_settings(&previous);
+        }
+
+        // Sections are kept in the conventional order of their kinds and, within the same kind,
+        // in insertion order
+        let index = self
+            .sections
+            .iter()
+            .position(|other| other.rank > section.rank)
+            .unwrap_or(self.sections.len());
+        self.sections.insert(index, section);
 
         Ok(())
     }
 
-    pub fn text<T: Execute, U: Read>(
+    fn add_section_next_to(
         &mut self,
-        vma: &Memory<T>,
-        lma: &Memory<U>,
+        name: &str,
+        anchor: &str,
+        after: bool,
+        vma: &MemoryId,
+        lma: &MemoryId,
         size: Option<Size>,
     ) -> Result<(), Error> {
-        self.sections.insert(
-            "text".to_owned(),
-            Section::new(lma.get_id().clone(), vma.get_id().clone(), size),
+        let mut section = Section::new(
+            name.to_owned(),
+            SectionKind::Custom,
+            vma.clone(),
+            lma.clone(),
+            size,
         );
+        if let Some(previous) = self.remove_section(name) {
+            section.keep_settings(&previous);
+        }
+
+        let index = self
+            .sections
+            .iter()
+            .position(|other| other.name == anchor)
+            .ok_or_else(|| Error::UnknownSection(anchor.to_owned()))?;
+        section.rank = self.sections[index].rank;
+        self.sections
+            .insert(if after { index + 1 } else { index }, section);
+
+        Ok(())
+    }
+
+    fn remove_section(&mut self, name: &str) -> Option<Section> {
+        let index = self
+            .sections
+            .iter()
+            .position(|section| section.name == name)?;
+        Some(self.sections.remove(index))
+    }
+
+    /// Iterates over the sections of the layout, in the order they are emitted in the linker
+    /// script.
+    pub fn sections(&self) -> impl Iterator<Item = &Section> {
+        self.sorted_sections().into_iter()
+    }
+
+    /// Looks up a section by name.
+    pub fn section(&self, name: &str) -> Option<&Section> {
+        self.sections.iter().find(|section| section.name == name)
+    }
+
+    /// The memory regions of the layout, in the order they were added.
+    pub fn regions(&self) -> impl Iterator<Item = &dyn MemoryRegion> {
+        self.memory_regions.iter().map(|region| region.as_ref())
+    }
+
+    /// Looks up a memory region by its id.
+    pub fn region(&self, id: &MemoryId) -> Option<&dyn MemoryRegion> {
+        self.memory_regions
+            .iter()
+            .find(|region| region.get_id() == id)
+            .map(|region| region.as_ref())
+    }
+
+    /// Places a previously added section at a fixed offset from the origin of its VMA region. The
+    /// section keeps its place in the order of the layout, so the sections after it in its region
+    /// follow it.
+    pub fn set_section_offset(&mut self, name: &str, offset: Size) -> Result<(), Error> {
+        let section = self
+            .sections
+            .iter_mut()
+            .find(|section| section.name == name)
+            .ok_or_else(|| Error::UnknownSection(name.to_owned()))?;
+        section.offset = Some(offset);
+
+        Ok(())
+    }
+
+    /// Aligns the start and the end of a previously added section. The alignment must be a power
+    /// of two.
+    pub fn set_section_alignment(&mut self, name: &str, alignment: Size) -> Result<(), Error> {
+        let section = self
+            .sections
+            .iter_mut()
+            .find(|section| section.name == name)
+            .ok_or_else(|| Error::UnknownSection(name.to_owned()))?;
+        if !alignment.0.is_power_of_two() {
+            return Err(Error::InvalidAlignment {
+                section: name.to_owned(),
+                alignment,
+            });
+        }
+        section.alignment = Some(alignment);
 
         Ok(())
     }
 
-    pub fn data<T: Execute, U: Read>(
+    // The bounds of the section methods reflect how the section is used at runtime: the VMA
+    // needs the access the code performs on the section, and the LMA only needs to be readable.
+
+    pub fn vector_table<T: Read, U: Read>(
         &mut self,
         vma: &Memory<T>,
         lma: &Memory<U>,
         size: Option<Size>,
     ) -> Result<(), Error> {
-        self.sections.insert(
-            "data".to_owned(),
-            Section::new(lma.get_id().clone(), vma.get_id().clone(), size),
-        );
-
-        Ok(())
+        self.add_section(
+            "vector_table",
+            SectionKind::VectorTable,
+            vma.get_id(),
+            lma.get_id(),
+            size,
+        )
     }
 
-    pub fn bss<T: Execute, U: Read>(
+    /// Places `.text`, which must run from the region it is loaded from, as the reset code is part
+    /// of it. Code that runs from RAM goes to `.ramfunc` instead.
+    pub fn text<T: Execute, U: Read>(
         &mut self,
         vma: &Memory<T>,
+        lma: &Memory<U>,
         size: Option<Size>,
     ) -> Result<(), Error> {
-        self.sections.insert(
-            "bss".to_owned(),
-            Section::new(vma.get_id().clone(), vma.get_id().clone(), size),
-        );
-
-        Ok(())
+        self.add_section("text", SectionKind::Text, vma.get_id(), lma.get_id(), size)
     }
 
-    pub fn stack<T: Execute, U: Read>(
+    pub fn rodata<T: Read, U: Read>(
         &mut self,
         vma: &Memory<T>,
         lma: &Memory<U>,
         size: Option<Size>,
     ) -> Result<(), Error> {
-        self.sections.insert(
-            "stack".to_owned(),
-            Section::new(lma.get_id().clone(), vma.get_id().clone(), size),
-        );
+        self.add_section(
+            "rodata",
+            SectionKind::Rodata,
+            vma.get_id(),
+            lma.get_id(),
+            size,
+        )
+    }
 
-        Ok(())
+    pub fn data<T: Write, U: Read>(
+        &mut self,
+        vma: &Memory<T>,
+        lma: &Memory<U>,
+        size: Option<Size>,
+    ) -> Result<(), Error> {
+        self.add_section("data", SectionKind::Data, vma.get_id(), lma.get_id(), size)
     }
 
-    pub fn uninit<T: Execute, U: Read>(
+    /// Places an initialized section in addition to `.data`, e.g. for another RAM. It collects the
+    /// `.<name>` input sections and is copied by the reset code like `.data`.
+    pub fn named_data<T: Write, U: Read>(
         &mut self,
+        name: &str,
         vma: &Memory<T>,
         lma: &Memory<U>,
         size: Option<Size>,
     ) -> Result<(), Error> {
-        self.sections.insert(
-            "uninit".to_owned(),
-            Section::new(lma.get_id().clone(), vma.get_id().clone(), size),
-        );
+        self.add_section(name, SectionKind::Data, vma.get_id(), lma.get_id(), size)
+    }
 
-        Ok(())
+    // The .bss, .heap, .stack and .uninit sections are not loaded, so they have no LMA
+
+    pub fn bss<T: Write>(&mut self, vma: &Memory<T>, size: Option<Size>) -> Result<(), Error> {
+        self.add_section("bss", SectionKind::Bss, vma.get_id(), vma.get_id(), size)
     }
 
-    pub fn ramfunc<T: Execute, U: Read>(
+    /// Places a zeroed section in addition to `.bss`, e.g. for another RAM. It collects the
+    /// `.<name>` input sections and is zeroed by the reset code like `.bss`.
+    pub fn named_bss<T: Write>(
         &mut self,
+        name: &str,
         vma: &Memory<T>,
-        lma: &Memory<U>,
         size: Option<Size>,
     ) -> Result<(), Error> {
-        self.sections.insert(
-            "ramfunc".to_owned(),
-            Section::new(lma.get_id().clone(), vma.get_id().clone(), size),
-        );
+        self.add_section(name, SectionKind::Bss, vma.get_id(), vma.get_id(), size)
+    }
 
-        Ok(())
+    pub fn stack<T: Write>(&mut self, vma: &Memory<T>, size: Option<Size>) -> Result<(), Error> {
+        self.named_stack("stack", vma, size)
     }
 
-    pub fn custom_section<T: Execute, U: Read>(
+    /// Places a stack in addition to the main one, e.g. for the threads of an RTOS or for another core.
+    /// Its initial stack pointer is the `_<name>_start` symbol.
+    pub fn named_stack<T: Write>(
         &mut self,
         name: &str,
         vma: &Memory<T>,
-        lma: &Memory<U>,
         size: Option<Size>,
     ) -> Result<(), Error> {
-        self.sections.insert(
-            name.to_owned(),
-            Section::new(lma.get_id().clone(), vma.get_id().clone(), size),
-        );
+        self.add_section(name, SectionKind::Stack, vma.get_id(), vma.get_id(), size)
+    }
+
+    /// Leaves a gap of `guard` bytes free below a previously added stack, e.g. to protect it
+    /// with the MPU. The gap is the `.<name>_guard` section.
+    pub fn set_stack_guard(&mut self, name: &str, guard: Size) -> Result<(), Error> {
+        self.stack_mut(name)?.guard = Some(guard);
 
         Ok(())
     }
 
-    pub fn generate(self, _output_dir: &std::path::Path) -> Result<(), Error> {
-        // Generate linker script in the given directory, along with the reset code.
+    /// Makes the reset code paint a previously added stack, and generates a
+    /// `<stack>_high_water_mark` function that measures how much of it has been used.
+    pub fn set_stack_painting(&mut self, name: &str, paint: bool) -> Result<(), Error> {
+        self.stack_mut(name)?.painted = paint;
 
         Ok(())
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::{Address, Error, MemoryLayout, Size};
+    fn stack_mut(&mut self, name: &str) -> Result<&mut Section, Error> {
+        let section = self
+            .sections
+            .iter_mut()
+            .find(|section| section.name == name)
+            .ok_or_else(|| Error::UnknownSection(name.to_owned()))?;
+        if section.kind != SectionKind::Stack {
+            return Err(Error::NotAStack(name.to_owned()));
+        }
 
-    #[test]
-    fn construct_multiple_mem_regions() {
-        let mut layout = MemoryLayout::new().unwrap();
-        let _ = layout
-            .add_rwx_region("FLASH", Address(0x00000000), Size(1024))
-            .unwrap();
-        let _ = layout
-            .add_rwx_region("FLASH", Address(0x10000000), Size(1024))
-            .unwrap();
+        Ok(section)
     }
 
-    #[test]
-    fn overlapping_memory_regions() {
-        let mut layout = MemoryLayout::new().unwrap();
-        let _ = layout
-            .add_rwx_region("FLASH", Address(0x00000000), Size(1024))
-            .unwrap();
-        match layout.add_rwx_region("RAM", Address(0x00000100), Size(1024)) {
-            Err(Error::OverlapingMemoryRegion(id)) => {
-                assert_eq!(id.0, "FLASH")
-            }
+    /// Places the heap. Without a size budget, the heap fills the space of the region that the
+    /// sections after it leave free, which requires all of them to have a size budget. Its bounds
+    /// are `_sheap` and `_eheap`.
+    pub fn heap<T: Write>(&mut self, vma: &Memory<T>, size: Option<Size>) -> Result<(), Error> {
+        self.add_section("heap", SectionKind::Heap, vma.get_id(), vma.get_id(), size)
+    }
+
+    /// Places the `.uninit` section, which the reset code neither copies nor zeroes. It starts
+    /// with a header of a magic word and a CRC-32 of the rest of the section, which tells a warm
+    /// reset from a cold boot (see [`MemoryLayout::generate_reset`]).
+    pub fn uninit<T: Write>(&mut self, vma: &Memory<T>, size: Option<Size>) -> Result<(), Error> {
+        self.add_section(
+            "uninit",
+            SectionKind::Uninit,
+            vma.get_id(),
+            vma.get_id(),
+            size,
+        )
+    }
+
+    /// Places `.ramfunc` in an executable RAM, to which the reset code copies it from `lma`.
+    /// Functions are put in it with the `#[ramfunc]` attribute of `ld_script_macros`, which also
+    /// explains how calls between flash and RAM reach each other.
+    pub fn ramfunc<T: Write + Execute, U: Read>(
+        &mut self,
+        vma: &Memory<T>,
+        lma: &Memory<U>,
+        size: Option<Size>,
+    ) -> Result<(), Error> {
+        self.add_section(
+            "ramfunc",
+            SectionKind::Ramfunc,
+            vma.get_id(),
+            lma.get_id(),
+            size,
+        )
+    }
+
+    pub fn custom_section<T: Read, U: Read>(
+        &mut self,
+        name: &str,
+        vma: &Memory<T>,
+        lma: &Memory<U>,
+        size: Option<Size>,
+    ) -> Result<(), Error> {
+        self.add_section(name, SectionKind::Custom, vma.get_id(), lma.get_id(), size)
+    }
+
+    /// Adds a custom section right before the already added section named `anchor`.
+    pub fn custom_section_before<T: Read, U: Read>(
+        &mut self,
+        name: &str,
+        anchor: &str,
+        vma: &Memory<T>,
+        lma: &Memory<U>,
+        size: Option<Size>,
+    ) -> Result<(), Error> {
+        self.add_section_next_to(name, anchor, false, vma.get_id(), lma.get_id(), size)
+    }
+
+    /// Adds a custom section right after the already added section named `anchor`.
+    pub fn custom_section_after<T: Read, U: Read>(
+        &mut self,
+        name: &str,
+        anchor: &str,
+        vma: &Memory<T>,
+        lma: &Memory<U>,
+        size: Option<Size>,
+    ) -> Result<(), Error> {
+        self.add_section_next_to(name, anchor, true, vma.get_id(), lma.get_id(), size)
+    }
+
+    /// Checks that the explicit size budgets of the sections fit in the regions they are placed
+    /// in. Sections are accounted for in their VMA region and, if they are loaded from a different
+    /// region, also in their LMA region.
+    fn validate(&self) -> Result<(), Error> {
+        // Memories might come from a different layout, so the regions and their access types are
+        // checked again here
+        for section in &self.sections {
+            for id in [&section.vma, &section.lma] {
+                if self.region(id).is_none() {
+                    return Err(Error::UnknownRegion {
+                        section: section.name.clone(),
+                        region: id.clone(),
+                    });
+                }
+            }
+
+            // `Reset` and the functions it calls run from `.text` before anything is copied
+            if section.kind == SectionKind::Text && section.is_copied() {
+                return Err(Error::CopiedText {
+                    vma: section.vma.clone(),
+                    lma: section.lma.clone(),
+                });
+            }
+
+            let vma = self.region(&section.vma).unwrap();
+            let required = section.required_access();
+            if !required
+                .chars()
+                .all(|access| vma.get_attributes().contains(access))
+            {
+                return Err(Error::PermissionMismatch {
+                    section: section.name.clone(),
+                    region: section.vma.clone(),
+                    required,
+                    available: vma.get_attributes(),
+                });
+            }
+
+            // The linker cannot pad a section placed at a fixed address to its alignment
+            if let Some(offset) = section.offset {
+                let alignment = section.alignment();
+                match vma.get_base_addres().checked_add(offset) {
+                    Some(address) if !address.is_aligned_to(alignment) => {
+                        return Err(Error::MisalignedSection {
+                            section: section.name.clone(),
+                            address,
+                            alignment,
+                        });
+                    }
+                    _ => {}
+                }
+            }
+
+            // Stacks are placed at fixed addresses when they are at the top of their region, and
+            // the first one starts at the origin of its region when they are at the bottom, so
+            // that it faults on overflow. Either way, only a single stack at the top of its region
+            // can do without a size budget.
+            if section.kind == SectionKind::Stack && section.offset.is_none() {
+                let shared = self.sections.iter().any(|s| {
+                    s.kind == SectionKind::Stack
+                        && s.vma == section.vma
+                        && !std::ptr::eq(s, section)
+                });
+                let address = match (self.stack_placement, section.size) {
+                    (placement, None) if shared || placement == StackPlacement::Bottom => {
+                        return Err(Error::UnsizedStack {
+                            section: section.name.clone(),
+                            region: section.vma.clone(),
+                        });
+                    }
+                    // A stack larger than its region is reported as an overflow below
+                    (StackPlacement::Top, Some(size)) => vma
+                        .get_size()
+                        .0
+                        .checked_sub(self.space_after(section).0)
+                        .and_then(|free| free.checked_sub(size.0))
+                        .map(|free| Address(vma.get_base_addres().0 + free)),
+                    (StackPlacement::Top, None) => None,
+                    (StackPlacement::Bottom, _) => self
+                        .sorted_sections()
+                        .into_iter()
+                        .find(|s| s.vma == section.vma)
+                        .filter(|first| std::ptr::eq(*first, section))
+                        .map(|_| {
+                            Address(vma.get_base_addres().0 + section.guard.map_or(0, |g| g.0))
+                        }),
+                };
+                if let Some(address) = address {
+                    if !address.is_aligned_to(section.alignment()) {
+                        return Err(Error::MisalignedSection {
+                            section: section.name.clone(),
+                            address,
+                            alignment: section.alignment(),
+                        });
+                    }
+                }
+            }
+
+            // A heap that fills its region needs to know how much space to leave to the sections
+            // after it
+            if section.kind == SectionKind::Heap && section.size.is_none() {
+                if let Some(other) = self.sections_after(section).find(|s| s.size.is_none()) {
+                    return Err(Error::UnsizedSectionAfterHeap {
+                        section: other.name.clone(),
+                        region: section.vma.clone(),
+                    });
+                }
+            }
+        }
+
+        for region in &self.memory_regions {
+            let id = region.get_id();
+            let requested = self
+                .sections
+                .iter()
+                .filter_map(|section| {
+                    let in_vma = section.vma == *id;
+                    let in_lma = !section.is_noload() && section.lma == *id;
+                    let guard = section.guard.map_or(0, |guard| guard.0);
+                    match section.size {
+                        Some(size) if in_vma || in_lma => Some(size.0.saturating_add(guard)),
+                        None if in_vma => Some(guard),
+                        _ => None,
+                    }
+                })
+                .fold(0u64, |total, size| total.saturating_add(size));
+
+            if requested > region.get_size().0 {
+                return Err(Error::RegionOverflow {
+                    region: id.clone(),
+                    requested: Size(requested),
+                    available: region.get_size(),
+                });
+            }
+
+            // Sections at fixed offsets must fit in the region and must not overlap each other.
+            // Sections without a size budget are considered empty.
+            let mut fixed: Vec<_> = self
+                .sorted_sections()
+                .into_iter()
+                .filter(|section| section.vma == *id)
+                .filter_map(|section| {
+                    let start = section.offset?.0;
+                    let end = start.saturating_add(section.size.map_or(0, |size| size.0));
+                    Some((&section.name, start, end))
+                })
+                .collect();
+            fixed.sort_by_key(|&(_, start, end)| (start, end));
+
+            for window in fixed.windows(2) {
+                let (first, first_start, first_end) = window[0];
+                let (second, second_start, _) = window[1];
+                if second_start < first_end || second_start == first_start {
+                    return Err(Error::OverlappingSections(first.clone(), second.clone()));
+                }
+            }
+            if let Some(&(_, _, end)) = fixed.last() {
+                if end > region.get_size().0 {
+                    return Err(Error::RegionOverflow {
+                        region: id.clone(),
+                        requested: Size(end),
+                        available: region.get_size(),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn render_memory(&self, script: &mut String) {
+        script.push_str("MEMORY\n{\n");
+        for region in &self.memory_regions {
+            // Writing to a String never fails
+            let _ = writeln!(
+                script,
+                "    {} ({}) : ORIGIN = {:#010x}, LENGTH = {:#010x}",
+                region.get_id().0,
+                region.get_attributes(),
+                region.get_base_addres().0,
+                region.get_size().0
+            );
+        }
+        script.push_str("}\n");
+    }
+
+    // Sections keep the order they are stored in, including the ones at fixed offsets, so that the
+    // sections after one of those in its region follow it. The stacks come first or last,
+    // depending on their placement.
+    fn sorted_sections(&self) -> Vec<&Section> {
+        let mut sections: Vec<_> = self.sections.iter().collect();
+        sections.sort_by_key(|section| match (section.kind, self.stack_placement) {
+            (SectionKind::Stack, StackPlacement::Bottom) => 0,
+            (SectionKind::Stack, StackPlacement::Top) => 2,
+            _ => 1,
+        });
+        sections
+    }
+
+    /// The sections rendered after `section` in the same region, not counting the ones at fixed
+    /// offsets.
+    fn sections_after<'a>(&'a self, section: &'a Section) -> impl Iterator<Item = &'a Section> {
+        self.sorted_sections()
+            .into_iter()
+            .skip_while(move |other| !std::ptr::eq(*other, section))
+            .skip(1)
+            .filter(move |other| other.vma == section.vma && other.offset.is_none())
+    }
+
+    /// The space taken up by the sections after `section` in its region.
+    fn space_after(&self, section: &Section) -> Size {
+        self.sections_after(section)
+            .map(Section::footprint)
+            .fold(Size(0), |total, size| total + size)
+    }
+
+    /// The section the copy and zero tables follow: `.text`, or else the first section that is
+    /// loaded, or else the first zeroed one.
+    fn tables_section(&self) -> Option<&Section> {
+        let sections = self.sorted_sections();
+        sections
+            .iter()
+            .find(|s| s.kind == SectionKind::Text)
+            .or_else(|| sections.iter().find(|s| !s.is_noload()))
+            .or_else(|| sections.iter().find(|s| s.is_zeroed()))
+            .copied()
+    }
+
+    fn render_sections(&self, script: &mut String) {
+        let tables = self.tables_section();
+        script.push_str("SECTIONS\n{\n");
+        for (index, section) in self.sorted_sections().into_iter().enumerate() {
+            if index != 0 {
+                script.push('\n');
+            }
+            section.render(script, self.space_after(section), self.stack_placement);
+            if tables.is_some_and(|tables| std::ptr::eq(tables, section)) {
+                self.render_tables(script, &section.lma);
+            }
+        }
+        script.push_str("}\n");
+    }
+
+    // The copy and zero tables are placed in the LMA region of the section they follow, so that
+    // they are loaded and can be read before any section is initialized. A record of the copy
+    // table holds the load address, start and end of an initialized section, and a record of the
+    // zero table the start and end of a zeroed section. Empty tables are left out.
+    fn render_tables(&self, script: &mut String, region: &MemoryId) {
+        let sections = self.sorted_sections();
+        let copied: Vec<_> = sections
+            .iter()
+            .filter(|s| s.is_copied())
+            .map(|s| {
+                vec![
+                    load_symbol(&s.name),
+                    start_symbol(&s.name),
+                    end_symbol(&s.name),
+                ]
+            })
+            .collect();
+        let zeroed: Vec<_> = sections
+            .iter()
+            .filter(|s| s.is_zeroed())
+            .map(|s| vec![start_symbol(&s.name), end_symbol(&s.name)])
+            .collect();
+
+        for (table, records) in [(COPY_TABLE, copied), (ZERO_TABLE, zeroed)] {
+            if records.is_empty() {
+                continue;
+            }
+            // Writing to a String never fails
+            script.push('\n');
+            let _ = writeln!(
+                script,
+                "    .{} : ALIGN({})",
+                table,
+                self.address_width.pointer_size()
+            );
+            script.push_str("    {\n");
+            let _ = writeln!(script, "        {} = .;", start_symbol(table));
+            for symbol in records.iter().flatten() {
+                let _ = writeln!(
+                    script,
+                    "        {}({});",
+                    self.address_width.pointer_directive(),
+                    symbol
+                );
+            }
+            let _ = writeln!(script, "        {} = .;", end_symbol(table));
+            let _ = writeln!(script, "    }} > {}", region.0);
+        }
+    }
+
+    /// Generates the `link.x` linker script in the given directory.
+    pub fn generate(&self, output_dir: &std::path::Path) -> Result<(), Error> {
+        self.validate()?;
+
+        let mut script = String::from("/* Generated by ld_script. Do not edit. */\n\n");
+        script.push_str("ENTRY(Reset);\n\n");
+        self.render_memory(&mut script);
+        script.push('\n');
+        self.render_sections(&mut script);
+
+        write_file(&output_dir.join("link.x"), &script)
+    }
+
+    /// Generates `reset.rs` in the given directory. It defines the `Reset` entry point, which
+    /// copies the initialized sections from their LMA to their VMA, zeroes the `.bss` sections and
+    /// then calls the user `main` function. It is meant to be used with `include!` from the
+    /// firmware crate.
+    ///
+    /// The sections to initialize are read from the copy and zero tables of the linker script,
+    /// which are placed after `.text`, or after the first loaded section in a layout without one.
+    ///
+    /// `.uninit` is left as is by `Reset`. The `uninit` and `seal_uninit` functions generated
+    /// along with it tell whether its contents survived a warm reset, e.g. for crash logs.
+    pub fn generate_reset(&self, output_dir: &std::path::Path) -> Result<(), Error> {
+        let sections = self.sorted_sections();
+        let copied = sections.iter().any(|s| s.is_copied());
+        let zeroed = sections.iter().any(|s| s.is_zeroed());
+        let painted: Vec<_> = sections.iter().filter(|s| s.painted).collect();
+
+        let mut code = String::from(RESET_PROLOGUE);
+        // Writing to a String never fails
+        for (table, records, used) in [(COPY_TABLE, 3, copied), (ZERO_TABLE, 2, zeroed)] {
+            if used {
+                let _ = writeln!(
+                    code,
+                    "        static {}: [*mut u32; {}];",
+                    start_symbol(table),
+                    records
+                );
+                let _ = writeln!(
+                    code,
+                    "        static {}: [*mut u32; {}];",
+                    end_symbol(table),
+                    records
+                );
+            }
+        }
+        for Section { name, .. } in &painted {
+            let _ = writeln!(code, "        static mut {}: u32;", start_symbol(name));
+            let _ = writeln!(
+                code,
+                "        static mut {}: u32;",
+                stack_start_symbol(name)
+            );
+        }
+        code.push_str(RESET_HELPERS);
+        if copied {
+            code.push_str(COPY_HELPER);
+        }
+        if zeroed {
+            code.push_str(ZERO_HELPER);
+        }
+
+        if copied {
+            let _ = write!(
+                code,
+                r#"    let mut record = ::core::ptr::addr_of!({start});
+    while record < ::core::ptr::addr_of!({end}) {{
+        unsafe {{
+            let [load, start, end] = *record;
+            copy(load, start, end);
+            record = record.add(1);
+        }}
+    }}
+"#,
+                start = start_symbol(COPY_TABLE),
+                end = end_symbol(COPY_TABLE)
+            );
+        }
+        if zeroed {
+            let _ = write!(
+                code,
+                r#"    let mut record = ::core::ptr::addr_of!({start});
+    while record < ::core::ptr::addr_of!({end}) {{
+        unsafe {{
+            let [start, end] = *record;
+            zero(start, end);
+            record = record.add(1);
+        }}
+    }}
+"#,
+                start = start_symbol(ZERO_TABLE),
+                end = end_symbol(ZERO_TABLE)
+            );
+        }
+        if !painted.is_empty() {
+            code.push_str(PAINT_PROLOGUE);
+        }
+        for Section { name, .. } in &painted {
+            let _ = writeln!(
+                code,
+                "    paint!(::core::ptr::addr_of_mut!({}), ::core::ptr::addr_of_mut!({}));",
+                start_symbol(name),
+                stack_start_symbol(name)
+            );
+        }
+        code.push_str(RESET_EPILOGUE);
+
+        if let Some(Section { name, .. }) = sections.iter().find(|s| s.kind == SectionKind::Heap) {
+            code.push_str(HEAP_PROLOGUE);
+            let _ = writeln!(code, "        static mut {}: u8;", start_symbol(name));
+            let _ = writeln!(code, "        static mut {}: u8;", end_symbol(name));
+            code.push_str("    }\n\n");
+            let _ = writeln!(
+                code,
+                "    let start = ::core::ptr::addr_of_mut!({});",
+                start_symbol(name)
+            );
+            let _ = writeln!(
+                code,
+                "    let end = ::core::ptr::addr_of_mut!({});",
+                end_symbol(name)
+            );
+            code.push_str(HEAP_EPILOGUE);
+        }
+
+        if let Some(Section { name, .. }) = sections.iter().find(|s| s.kind == SectionKind::Uninit)
+        {
+            code.push_str(UNINIT_PROLOGUE);
+            let _ = writeln!(code, "        static mut {}: u32;", start_symbol(name));
+            let _ = writeln!(code, "        static mut {}: u32;", end_symbol(name));
+            code.push_str("    }\n\n");
+            let _ = writeln!(
+                code,
+                "    let start = ::core::ptr::addr_of_mut!({});",
+                start_symbol(name)
+            );
+            let _ = writeln!(
+                code,
+                "    let end = ::core::ptr::addr_of_mut!({});",
+                end_symbol(name)
+            );
+            code.push_str(UNINIT_EPILOGUE);
+        }
+
+        if !painted.is_empty() {
+            code.push_str(HIGH_WATER_MARK_HELPERS);
+        }
+        for Section { name, .. } in &painted {
+            let _ = write!(
+                code,
+                r#"
+/// The high-water mark of `.{name}` in bytes.
+pub fn {name}_high_water_mark() -> usize {{
+    unsafe extern "C" {{
+        static {bottom}: u32;
+        static {top}: u32;
+    }}
+
+    unsafe {{ high_water_mark(::core::ptr::addr_of!({bottom}), ::core::ptr::addr_of!({top})) }}
+}}
+"#,
+                name = name,
+                bottom = start_symbol(name),
+                top = stack_start_symbol(name)
+            );
+        }
+
+        write_file(&output_dir.join("reset.rs"), &code)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Address, AddressWidth, Error, MemoryLayout, Size, StackPlacement};
+
+    #[test]
+    fn construct_multiple_mem_regions() {
+        let mut layout = MemoryLayout::new().unwrap();
+        let _ = layout
+            .add_rwx_region("FLASH", Address(0x00000000), Size(1024))
+            .unwrap();
+        let _ = layout
+            .add_rwx_region("RAM", Address(0x10000000), Size(1024))
+            .unwrap();
+    }
+
+    #[test]
+    fn duplicate_memory_region_names() {
+        let mut layout = MemoryLayout::new().unwrap();
+        let _ = layout
+            .add_rwx_region("FLASH", Address(0x00000000), Size(1024))
+            .unwrap();
+        match layout.add_rwx_region("flash", Address(0x10000000), Size(1024)) {
+            Err(Error::DuplicateRegion(id)) => {
+                assert_eq!(id.0, "FLASH")
+            }
+            _ => {
+                panic!()
+            }
+        };
+    }
+
+    #[test]
+    fn overlapping_memory_regions() {
+        let mut layout = MemoryLayout::new().unwrap();
+        let _ = layout
+            .add_rwx_region("FLASH", Address(0x00000000), Size(1024))
+            .unwrap();
+        match layout.add_rwx_region("RAM", Address(0x00000100), Size(1024)) {
+            Err(Error::OverlapingMemoryRegion { existing, .. }) => {
+                assert_eq!(existing.0, "FLASH")
+            }
+            _ => {
+                panic!()
+            }
+        };
+    }
+
+    #[test]
+    fn overlapping_identical_memory_regions() {
+        let mut layout = MemoryLayout::new().unwrap();
+        let _ = layout
+            .add_rwx_region("FLASH", Address(0x00000000), Size(1024))
+            .unwrap();
+        match layout.add_rwx_region("RAM", Address(0x00000000), Size(1024)) {
+            Err(Error::OverlapingMemoryRegion { existing, .. }) => {
+                assert_eq!(existing.0, "FLASH")
+            }
+            _ => {
+                panic!()
+            }
+        };
+    }
+
+    #[test]
+    fn overlapping_nested_memory_regions() {
+        let mut layout = MemoryLayout::new().unwrap();
+        let _ = layout
+            .add_rwx_region("FLASH", Address(0x00000000), Size(1024))
+            .unwrap();
+        assert!(layout
+            .add_rwx_region("RAM", Address(0x00000100), Size(256))
+            .is_err());
+        assert!(layout
+            .add_rwx_region("RAM", Address(0x00000000), Size(2048))
+            .is_err());
+        assert!(layout
+            .add_rwx_region("RAM", Address(0x00000000), Size(1))
+            .is_err());
+        assert!(layout
+            .add_rwx_region("RAM", Address(0x000003ff), Size(1))
+            .is_err());
+    }
+
+    #[test]
+    fn touching_memory_regions() {
+        let mut layout = MemoryLayout::new().unwrap();
+        let _ = layout
+            .add_rwx_region("RAM1", Address(0x20000000), Size(1024))
+            .unwrap();
+        let _ = layout
+            .add_rwx_region("RAM2", Address(0x20000400), Size(1024))
+            .unwrap();
+        let _ = layout
+            .add_rwx_region("RAM0", Address(0x1ffffc00), Size(1024))
+            .unwrap();
+    }
+
+    #[test]
+    fn memory_region_at_end_of_address_space() {
+        let mut layout = MemoryLayout::new().unwrap();
+        let _ = layout
+            .add_rwx_region("TOP", Address(0xffff0000), Size(0x10000))
+            .unwrap();
+        match layout.add_rwx_region("WRAP", Address(0xfffff000), Size(0x2000)) {
+            Err(Error::AddressWrapAround(id)) => {
+                assert_eq!(id.0, "WRAP")
+            }
+            _ => {
+                panic!()
+            }
+        };
+    }
+
+    #[test]
+    fn memory_region_above_4_gib() {
+        let mut layout = MemoryLayout::new().unwrap();
+        match layout.add_rwx_region("HIGH", Address(0x1_0000_0000), Size(0x1000)) {
+            Err(Error::AddressWrapAround(id)) => {
+                assert_eq!(id.0, "HIGH")
+            }
+            _ => {
+                panic!()
+            }
+        };
+        match layout.add_rwx_region("HUGE", Address(0x80000000), Size(0x1_0000_0000)) {
+            Err(Error::AddressWrapAround(id)) => {
+                assert_eq!(id.0, "HUGE")
+            }
+            _ => {
+                panic!()
+            }
+        };
+
+        let mut layout = MemoryLayout::with_address_width(AddressWidth::Bits64).unwrap();
+        let _ = layout
+            .add_rwx_region("HIGH", Address(0x1_0000_0000), Size(0x1000))
+            .unwrap();
+        let _ = layout
+            .add_rwx_region("TOP", Address(0xffff_ffff_ffff_0000), Size(0x10000))
+            .unwrap();
+        match layout.add_rwx_region("LOW", Address(0xffff_f000), Size(0x2000)) {
+            Err(Error::OverlapingMemoryRegion { existing, .. }) => {
+                assert_eq!(existing.0, "HIGH")
+            }
+            _ => {
+                panic!()
+            }
+        };
+    }
+
+    #[test]
+    fn zero_sized_memory_region() {
+        let mut layout = MemoryLayout::new().unwrap();
+        match layout.add_rwx_region("EMPTY", Address(0x20000000), Size(0)) {
+            Err(Error::ZeroSizedRegion(id)) => {
+                assert_eq!(id.0, "EMPTY")
+            }
             _ => {
                 panic!()
             }
@@ -353,8 +1717,375 @@ mod tests {
         let ram = layout
             .add_rwx_region("RAM", Address(0x00001000), Size(1024))
             .unwrap();
-        layout.text(&ram, &flash, None).unwrap();
-        let path: std::path::PathBuf = "".into();
+        layout.text(&ram, &ram, None).unwrap();
+        layout.data(&ram, &flash, None).unwrap();
+        let path = std::env::temp_dir().join("ld_script_missing_sections");
+        std::fs::create_dir_all(&path).unwrap();
         layout.generate(&path).unwrap();
     }
+
+    #[test]
+    fn sections_at_fixed_offsets() {
+        let mut layout = MemoryLayout::new().unwrap();
+        let flash = layout
+            .add_rx_region("FLASH", Address(0x00000000), Size(4096))
+            .unwrap();
+        layout
+            .vector_table(&flash, &flash, Some(Size(0x200)))
+            .unwrap();
+        layout
+            .custom_section("header", &flash, &flash, Some(Size(0x100)))
+            .unwrap();
+        layout.set_section_offset("vector_table", Size(0)).unwrap();
+        layout.set_section_offset("header", Size(0x200)).unwrap();
+        layout.validate().unwrap();
+    }
+
+    #[test]
+    fn overlapping_sections_at_fixed_offsets() {
+        let mut layout = MemoryLayout::new().unwrap();
+        let flash = layout
+            .add_rx_region("FLASH", Address(0x00000000), Size(4096))
+            .unwrap();
+        layout
+            .vector_table(&flash, &flash, Some(Size(0x400)))
+            .unwrap();
+        layout
+            .custom_section("header", &flash, &flash, Some(Size(0x100)))
+            .unwrap();
+        layout.set_section_offset("vector_table", Size(0)).unwrap();
+        layout.set_section_offset("header", Size(0x200)).unwrap();
+        match layout.validate() {
+            Err(Error::OverlappingSections(first, second)) => {
+                assert_eq!(first, "vector_table");
+                assert_eq!(second, "header");
+            }
+            _ => {
+                panic!()
+            }
+        };
+    }
+
+    #[test]
+    fn offset_of_unknown_section() {
+        let mut layout = MemoryLayout::new().unwrap();
+        match layout.set_section_offset("header", Size(0x200)) {
+            Err(Error::UnknownSection(name)) => {
+                assert_eq!(name, "header")
+            }
+            _ => {
+                panic!()
+            }
+        };
+    }
+
+    #[test]
+    fn misaligned_section_at_fixed_offset() {
+        let mut layout = MemoryLayout::new().unwrap();
+        let flash = layout
+            .add_rx_region("FLASH", Address(0x00000100), Size(4096))
+            .unwrap();
+        layout
+            .vector_table(&flash, &flash, Some(Size(0x200)))
+            .unwrap();
+        layout.set_section_offset("vector_table", Size(0)).unwrap();
+        layout
+            .set_section_alignment("vector_table", Size(0x200))
+            .unwrap();
+        match layout.validate() {
+            Err(Error::MisalignedSection {
+                section,
+                address,
+                alignment,
+            }) => {
+                assert_eq!(section, "vector_table");
+                assert_eq!(address, Address(0x100));
+                assert_eq!(alignment, Size(0x200));
+            }
+            _ => {
+                panic!()
+            }
+        };
+
+        layout
+            .set_section_offset("vector_table", Size(0x100))
+            .unwrap();
+        layout.validate().unwrap();
+    }
+
+    #[test]
+    fn alignment_must_be_a_power_of_two() {
+        let mut layout = MemoryLayout::new().unwrap();
+        let ram = layout
+            .add_rw_region("RAM", Address(0x20000000), Size(4096))
+            .unwrap();
+        layout.bss(&ram, None).unwrap();
+        for alignment in [0, 24] {
+            match layout.set_section_alignment("bss", Size(alignment)) {
+                Err(Error::InvalidAlignment { section, .. }) => {
+                    assert_eq!(section, "bss")
+                }
+                _ => {
+                    panic!()
+                }
+            };
+        }
+
+        // Explicit alignments never weaken the one the section needs
+        layout.set_section_alignment("bss", Size(2)).unwrap();
+        assert_eq!(layout.section("bss").unwrap().alignment(), Size(4));
+        layout.set_section_alignment("bss", Size(32)).unwrap();
+        assert_eq!(layout.section("bss").unwrap().alignment(), Size(32));
+    }
+
+    #[test]
+    fn heap_filling_region_needs_budgets_after_it() {
+        let mut layout = MemoryLayout::new().unwrap();
+        let ram = layout
+            .add_rw_region("RAM", Address(0x20000000), Size(4096))
+            .unwrap();
+        layout.heap(&ram, None).unwrap();
+        layout.stack(&ram, None).unwrap();
+        match layout.validate() {
+            Err(Error::UnsizedSectionAfterHeap { section, region }) => {
+                assert_eq!(section, "stack");
+                assert_eq!(region.0, "RAM");
+            }
+            _ => {
+                panic!()
+            }
+        };
+
+        layout.stack(&ram, Some(Size(1000))).unwrap();
+        layout.validate().unwrap();
+        // The stack is rounded up to its alignment
+        let heap = layout.section("heap").unwrap();
+        assert_eq!(layout.space_after(heap), Size(1000));
+        layout.stack(&ram, Some(Size(1001))).unwrap();
+        let heap = layout.section("heap").unwrap();
+        assert_eq!(layout.space_after(heap), Size(1008));
+    }
+
+    #[test]
+    fn stack_placement_is_validated() {
+        let mut layout = MemoryLayout::new().unwrap();
+        let ram = layout
+            .add_rw_region("RAM", Address(0x20000000), Size(4096))
+            .unwrap();
+        layout.stack(&ram, None).unwrap();
+        layout.validate().unwrap();
+
+        layout.set_stack_placement(StackPlacement::Bottom);
+        match layout.validate() {
+            Err(Error::UnsizedStack { section, region }) => {
+                assert_eq!(section, "stack");
+                assert_eq!(region.0, "RAM");
+            }
+            _ => {
+                panic!()
+            }
+        };
+        layout.stack(&ram, Some(Size(1024))).unwrap();
+        layout.validate().unwrap();
+
+        // At the top of the region, the start of the stack depends on its size
+        layout.set_stack_placement(StackPlacement::Top);
+        layout.stack(&ram, Some(Size(1020))).unwrap();
+        match layout.validate() {
+            Err(Error::MisalignedSection {
+                section, address, ..
+            }) => {
+                assert_eq!(section, "stack");
+                assert_eq!(address, Address(0x20000c04));
+            }
+            _ => {
+                panic!()
+            }
+        };
+
+        layout.stack(&ram, Some(Size(8192))).unwrap();
+        match layout.validate() {
+            Err(Error::RegionOverflow { region, .. }) => {
+                assert_eq!(region.0, "RAM")
+            }
+            _ => {
+                panic!()
+            }
+        };
+    }
+
+    #[test]
+    fn stacks_sharing_a_region_need_budgets() {
+        let mut layout = MemoryLayout::new().unwrap();
+        let ram = layout
+            .add_rw_region("RAM", Address(0x20000000), Size(0x4000))
+            .unwrap();
+        layout.stack(&ram, None).unwrap();
+        layout.named_stack("psp", &ram, Some(Size(0x1000))).unwrap();
+        match layout.validate() {
+            Err(Error::UnsizedStack { section, region }) => {
+                assert_eq!(section, "stack");
+                assert_eq!(region.0, "RAM");
+            }
+            _ => {
+                panic!()
+            }
+        };
+
+        // Guards count towards the space of the region
+        layout.stack(&ram, Some(Size(0x1000))).unwrap();
+        layout.set_stack_guard("psp", Size(0x2000)).unwrap();
+        layout.validate().unwrap();
+        layout.set_stack_guard("stack", Size(0x100)).unwrap();
+        match layout.validate() {
+            Err(Error::RegionOverflow { requested, .. }) => {
+                assert_eq!(requested, Size(0x4100))
+            }
+            _ => {
+                panic!()
+            }
+        };
+
+        layout.bss(&ram, None).unwrap();
+        match layout.set_stack_guard("bss", Size(0x100)) {
+            Err(Error::NotAStack(name)) => {
+                assert_eq!(name, "bss")
+            }
+            _ => {
+                panic!()
+            }
+        };
+    }
+
+    #[test]
+    fn section_in_region_of_another_layout() {
+        let mut other = MemoryLayout::new().unwrap();
+        let sram = other
+            .add_rw_region("SRAM", Address(0x20000000), Size(1024))
+            .unwrap();
+
+        let mut layout = MemoryLayout::new().unwrap();
+        layout.bss(&sram, None).unwrap();
+        match layout.validate() {
+            Err(Error::UnknownRegion { section, region }) => {
+                assert_eq!(section, "bss");
+                assert_eq!(region.0, "SRAM");
+            }
+            _ => {
+                panic!()
+            }
+        };
+    }
+
+    #[test]
+    fn copied_custom_section_in_read_only_region() {
+        let mut layout = MemoryLayout::new().unwrap();
+        let flash = layout
+            .add_rx_region("FLASH", Address(0x00000000), Size(1024))
+            .unwrap();
+        let rom = layout
+            .add_rx_region("ROM", Address(0x10000000), Size(1024))
+            .unwrap();
+        layout.custom_section("config", &rom, &flash, None).unwrap();
+        match layout.validate() {
+            Err(error @ Error::PermissionMismatch { .. }) => {
+                assert_eq!(
+                    error.to_string(),
+                    "section `.config` needs `w` access, but memory region `ROM` is `rx`"
+                );
+            }
+            _ => {
+                panic!()
+            }
+        };
+    }
+
+    #[test]
+    fn text_is_not_copied() {
+        let mut layout = MemoryLayout::new().unwrap();
+        let flash = layout
+            .add_rx_region("FLASH", Address(0x00000000), Size(1024))
+            .unwrap();
+        let ram = layout
+            .add_rwx_region("RAM", Address(0x20000000), Size(1024))
+            .unwrap();
+        layout.text(&ram, &flash, None).unwrap();
+        match layout.validate() {
+            Err(Error::CopiedText { vma, lma }) => {
+                assert_eq!(vma.0, "RAM");
+                assert_eq!(lma.0, "FLASH");
+            }
+            _ => {
+                panic!()
+            }
+        };
+
+        // Code that runs from RAM goes to `.ramfunc` instead
+        layout.text(&flash, &flash, None).unwrap();
+        layout.ramfunc(&ram, &flash, None).unwrap();
+        layout.validate().unwrap();
+    }
+
+    #[test]
+    fn section_budgets_fill_region() {
+        let mut layout = MemoryLayout::new().unwrap();
+        let flash = layout
+            .add_rx_region("FLASH", Address(0x00000000), Size(1024))
+            .unwrap();
+        let ram = layout
+            .add_rwx_region("RAM", Address(0x20000000), Size(1024))
+            .unwrap();
+        layout.text(&flash, &flash, Some(Size(768))).unwrap();
+        layout.data(&ram, &flash, Some(Size(256))).unwrap();
+        layout.bss(&ram, Some(Size(768))).unwrap();
+        layout.validate().unwrap();
+    }
+
+    #[test]
+    fn section_budgets_overflow_vma_region() {
+        let mut layout = MemoryLayout::new().unwrap();
+        let flash = layout
+            .add_rx_region("FLASH", Address(0x00000000), Size(1024))
+            .unwrap();
+        let ccram = layout
+            .add_rwx_region("CCRAM", Address(0x10000000), Size(1024))
+            .unwrap();
+        layout.data(&ccram, &flash, Some(Size(512))).unwrap();
+        layout.bss(&ccram, Some(Size(768))).unwrap();
+        match layout.validate() {
+            Err(Error::RegionOverflow {
+                region,
+                requested,
+                available,
+            }) => {
+                assert_eq!(region.0, "CCRAM");
+                assert_eq!(requested.0, 1280);
+                assert_eq!(available.0, 1024);
+            }
+            _ => {
+                panic!()
+            }
+        };
+    }
+
+    #[test]
+    fn section_budgets_overflow_lma_region() {
+        let mut layout = MemoryLayout::new().unwrap();
+        let flash = layout
+            .add_rx_region("FLASH", Address(0x00000000), Size(1024))
+            .unwrap();
+        let ram = layout
+            .add_rwx_region("RAM", Address(0x20000000), Size(4096))
+            .unwrap();
+        layout.text(&flash, &flash, Some(Size(768))).unwrap();
+        layout.data(&ram, &flash, Some(Size(512))).unwrap();
+        match layout.validate() {
+            Err(Error::RegionOverflow { region, .. }) => {
+                assert_eq!(region.0, "FLASH")
+            }
+            _ => {
+                panic!()
+            }
+        };
+    }
 }
@@ -0,0 +1,364 @@
+use std::fmt;
+use std::ops::{Add, Sub};
+use std::str::FromStr;
+
+use crate::Error;
+
+/// Width of the address space of the target.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum AddressWidth {
+    #[default]
+    Bits32,
+    Bits64,
+}
+
+impl AddressWidth {
+    /// One past the highest address, where a region at the very top of the address space ends.
+    pub(crate) const fn end(self) -> u128 {
+        match self {
+            AddressWidth::Bits32 => 1 << 32,
+            AddressWidth::Bits64 => 1 << 64,
+        }
+    }
+
+    /// The size of a pointer, i.e. of the entries of the copy and zero tables.
+    pub(crate) const fn pointer_size(self) -> u64 {
+        match self {
+            AddressWidth::Bits32 => 4,
+            AddressWidth::Bits64 => 8,
+        }
+    }
+
+    /// The linker script command that stores a pointer.
+    pub(crate) const fn pointer_directive(self) -> &'static str {
+        match self {
+            AddressWidth::Bits32 => "LONG",
+            AddressWidth::Bits64 => "QUAD",
+        }
+    }
+}
+
+/// An address in the address space of the target.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Address(pub(crate) u64);
+
+impl Address {
+    pub const fn new(addr: u64) -> Self {
+        Self(addr)
+    }
+
+    pub const fn value(self) -> u64 {
+        self.0
+    }
+
+    /// Returns the address `size` bytes past this one, or `None` if it does not fit in the
+    /// address space.
+    pub const fn checked_add(self, size: Size) -> Option<Address> {
+        match self.0.checked_add(size.0) {
+            Some(addr) => Some(Address(addr)),
+            None => None,
+        }
+    }
+
+    /// Returns the distance from `other` to this address, or `None` if `other` is above it.
+    pub const fn checked_sub(self, other: Address) -> Option<Size> {
+        match self.0.checked_sub(other.0) {
+            Some(size) => Some(Size(size)),
+            None => None,
+        }
+    }
+
+    /// Whether the address is a multiple of `alignment`. Everything is aligned to zero bytes.
+    pub const fn is_aligned_to(self, alignment: Size) -> bool {
+        alignment.0 == 0 || self.0.is_multiple_of(alignment.0)
+    }
+}
+
+impl Add<Size> for Address {
+    type Output = Address;
+
+    fn add(self, size: Size) -> Address {
+        self.checked_add(size)
+            .expect("address overflows the address space")
+    }
+}
+
+impl Sub for Address {
+    type Output = Size;
+
+    fn sub(self, other: Address) -> Size {
+        self.checked_sub(other)
+            .expect("subtracted address is above the address it is subtracted from")
+    }
+}
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#010x}", self.0)
+    }
+}
+
+/// A size in bytes.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Size(pub(crate) u64);
+
+impl Size {
+    pub const KIB: Size = Size(1 << 10);
+    pub const MIB: Size = Size(1 << 20);
+    pub const GIB: Size = Size(1 << 30);
+    pub const KB: Size = Size(1_000);
+    pub const MB: Size = Size(1_000_000);
+    pub const GB: Size = Size(1_000_000_000);
+
+    pub const fn new(size: u64) -> Self {
+        Self(size)
+    }
+
+    pub const fn value(self) -> u64 {
+        self.0
+    }
+
+    pub const fn checked_add(self, other: Size) -> Option<Size> {
+        match self.0.checked_add(other.0) {
+            Some(size) => Some(Size(size)),
+            None => None,
+        }
+    }
+
+    /// Returns `count` times this size, or `None` if it does not fit. This is the fallible
+    /// counterpart of the unit constructors, e.g. `Size::MIB.checked_mul(count)`.
+    pub const fn checked_mul(self, count: u64) -> Option<Size> {
+        match self.0.checked_mul(count) {
+            Some(size) => Some(Size(size)),
+            None => None,
+        }
+    }
+
+    /// `count` times `unit`. Panics on overflow, which is a compile-time error in a const
+    /// context.
+    const fn scaled(unit: Size, count: u64) -> Size {
+        match unit.checked_mul(count) {
+            Some(size) => size,
+            None => panic!("size overflows"),
+        }
+    }
+
+    pub const fn kib(count: u64) -> Size {
+        Self::scaled(Self::KIB, count)
+    }
+
+    pub const fn mib(count: u64) -> Size {
+        Self::scaled(Self::MIB, count)
+    }
+
+    pub const fn gib(count: u64) -> Size {
+        Self::scaled(Self::GIB, count)
+    }
+
+    pub const fn kb(count: u64) -> Size {
+        Self::scaled(Self::KB, count)
+    }
+
+    pub const fn mb(count: u64) -> Size {
+        Self::scaled(Self::MB, count)
+    }
+
+    pub const fn gb(count: u64) -> Size {
+        Self::scaled(Self::GB, count)
+    }
+}
+
+impl Add for Size {
+    type Output = Size;
+
+    fn add(self, other: Size) -> Size {
+        self.checked_add(other).expect("size overflows")
+    }
+}
+
+/// Sizes are printed in the largest binary unit that represents them exactly, e.g. `256 KiB`.
+impl fmt::Display for Size {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const UNITS: [(u64, &str); 3] = [(1 << 30, "GiB"), (1 << 20, "MiB"), (1 << 10, "KiB")];
+
+        match UNITS
+            .iter()
+            .find(|&&(unit, _)| self.0 != 0 && self.0.is_multiple_of(unit))
+        {
+            Some((unit, name)) => write!(f, "{} {}", self.0 / unit, name),
+            None if self.0 == 1 => write!(f, "1 byte"),
+            None => write!(f, "{} bytes", self.0),
+        }
+    }
+}
+
+/// Parses sizes as written in linker scripts and configuration files: `0x4000`, `256K`, `1M`,
+/// `16 KiB` or `1 MB`. `K`, `M` and `G` are binary units, like in the `MEMORY` command, while
+/// `kB`, `MB` and `GB` are decimal.
+impl FromStr for Size {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Size, Error> {
+        let invalid = || Error::InvalidSize(s.to_owned());
+        let input = s.trim();
+
+        if let Some(hex) = input
+            .strip_prefix("0x")
+            .or_else(|| input.strip_prefix("0X"))
+        {
+            return u64::from_str_radix(&hex.replace('_', ""), 16)
+                .map(Size)
+                .map_err(|_| invalid());
+        }
+
+        let split = input
+            .find(|c: char| !c.is_ascii_digit() && c != '_')
+            .unwrap_or(input.len());
+        let (digits, unit) = input.split_at(split);
+        let count: u64 = digits.replace('_', "").parse().map_err(|_| invalid())?;
+        let unit = match unit.trim_start() {
+            "" | "B" | "byte" | "bytes" => Size(1),
+            "K" | "k" | "KiB" => Size::KIB,
+            "M" | "MiB" => Size::MIB,
+            "G" | "GiB" => Size::GIB,
+            "kB" | "KB" => Size::KB,
+            "MB" => Size::MB,
+            "GB" => Size::GB,
+            _ => return Err(invalid()),
+        };
+        unit.checked_mul(count).ok_or_else(invalid)
+    }
+}
+
+/// Unit constructors for sizes. Any `u32` count of these units fits in a [`Size`]; for wider
+/// counts use the const functions such as [`Size::kib`], which are checked at compile time in
+/// a const context, or [`Size::checked_mul`] with one of the unit constants.
+pub trait U32Ext {
+    fn bytes(self) -> Size;
+    /// Same as [`U32Ext::kib`].
+    fn kilobytes(self) -> Size;
+    /// Same as [`U32Ext::mib`].
+    fn megabytes(self) -> Size;
+    /// Same as [`U32Ext::gib`].
+    fn gigabytes(self) -> Size;
+    fn kib(self) -> Size;
+    fn mib(self) -> Size;
+    fn gib(self) -> Size;
+    fn kb(self) -> Size;
+    fn mb(self) -> Size;
+    fn gb(self) -> Size;
+}
+
+impl U32Ext for u32 {
+    fn bytes(self) -> Size {
+        Size(u64::from(self))
+    }
+    fn kilobytes(self) -> Size {
+        Size::kib(u64::from(self))
+    }
+    fn megabytes(self) -> Size {
+        Size::mib(u64::from(self))
+    }
+    fn gigabytes(self) -> Size {
+        Size::gib(u64::from(self))
+    }
+    fn kib(self) -> Size {
+        Size::kib(u64::from(self))
+    }
+    fn mib(self) -> Size {
+        Size::mib(u64::from(self))
+    }
+    fn gib(self) -> Size {
+        Size::gib(u64::from(self))
+    }
+    fn kb(self) -> Size {
+        Size::kb(u64::from(self))
+    }
+    fn mb(self) -> Size {
+        Size::mb(u64::from(self))
+    }
+    fn gb(self) -> Size {
+        Size::gb(u64::from(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Address, Size, U32Ext};
+
+    #[test]
+    fn address_arithmetic() {
+        let base = Address::new(0x20000000);
+        assert_eq!(base + 64.kilobytes(), Address::new(0x20010000));
+        assert_eq!(Address::new(0x20010000) - base, 64.kilobytes());
+        assert_eq!(
+            Address::new(0xffffffff).checked_add(1.bytes()),
+            Some(Address::new(1 << 32))
+        );
+        assert_eq!(Address::new(u64::MAX).checked_add(1.bytes()), None);
+        assert_eq!(base.checked_sub(Address::new(0x20000001)), None);
+        assert!(base.is_aligned_to(512.megabytes()));
+        assert!(!Address::new(0x20000004).is_aligned_to(8.bytes()));
+    }
+
+    #[test]
+    fn size_arithmetic() {
+        assert_eq!(16.kilobytes() + 16.kilobytes(), 32.kilobytes());
+        assert_eq!(Size::new(u64::MAX).checked_add(1.bytes()), None);
+        assert!(1.megabytes() > 1023.kilobytes());
+        assert_eq!(2.kb(), Size::new(2_000));
+        assert_eq!(3.mib(), Size::new(3 << 20));
+        assert_eq!(8192.megabytes(), 8.gigabytes());
+        assert_eq!(Size::GIB.checked_mul(1 << 34), None);
+
+        const RAM: Size = Size::kib(192);
+        assert_eq!(RAM, 192.kilobytes());
+    }
+
+    #[test]
+    #[should_panic(expected = "size overflows")]
+    fn unit_overflow_panics() {
+        Size::gib(1 << 34);
+    }
+
+    #[test]
+    fn parsing() {
+        assert_eq!("256K".parse::<Size>().unwrap(), 256.kib());
+        assert_eq!("1M".parse::<Size>().unwrap(), 1.mib());
+        assert_eq!("0x4000".parse::<Size>().unwrap(), 16.kib());
+        assert_eq!("512".parse::<Size>().unwrap(), 512.bytes());
+        assert_eq!(" 4 kB ".parse::<Size>().unwrap(), 4.kb());
+        assert_eq!("1_000_000".parse::<Size>().unwrap(), 1.mb());
+        assert_eq!("1G".parse::<Size>().unwrap(), 1.gib());
+        assert_eq!("0x1_0000_0000".parse::<Size>().unwrap(), 4.gib());
+
+        for size in [1.bytes(), 1536.bytes(), 16.kib(), 2.mib(), 1.gib()] {
+            assert_eq!(size.to_string().parse::<Size>().unwrap(), size);
+        }
+
+        for invalid in [
+            "",
+            "K",
+            "0x",
+            "-1",
+            "1.5M",
+            "4 KiBs",
+            "0x10K",
+            "17179869184G",
+        ] {
+            assert!(invalid.parse::<Size>().is_err(), "{:?} parsed", invalid);
+        }
+    }
+
+    #[test]
+    fn formatting() {
+        const FLASH: Address = Address::new(0x08000000);
+        assert_eq!(FLASH.to_string(), "0x08000000");
+        assert_eq!(256.kilobytes().to_string(), "256 KiB");
+        assert_eq!(2.megabytes().to_string(), "2 MiB");
+        assert_eq!(Size::new(1 << 30).to_string(), "1 GiB");
+        assert_eq!(1536.bytes().to_string(), "1536 bytes");
+        assert_eq!(1.bytes().to_string(), "1 byte");
+        assert_eq!(0.bytes().to_string(), "0 bytes");
+    }
+}
@@ -0,0 +1,28 @@
+// Input sections for the scripts linked by the tests. lld places orphan sections wherever it sees
+// fit, so each test only picks the ones its script places, with `--cfg`.
+#![no_std]
+#![allow(non_upper_case_globals)]
+
+#[no_mangle]
+#[link_section = ".text.Reset"]
+pub static Reset: [u8; 0x40] = [0; 0x40];
+
+#[cfg(vector_table)]
+#[no_mangle]
+#[link_section = ".vector_table.reset"]
+pub static VECTORS: [u32; 4] = [0; 4];
+
+#[cfg(ramfunc)]
+#[no_mangle]
+#[link_section = ".ramfunc.fixture"]
+pub static RAMFUNC: [u8; 0x20] = [0; 0x20];
+
+#[cfg(data)]
+#[no_mangle]
+#[link_section = ".data.fixture"]
+pub static DATA: [u8; 0x10] = [1; 0x10];
+
+#[cfg(config)]
+#[no_mangle]
+#[link_section = ".config.fixture"]
+pub static CONFIG: [u8; 0x8] = [1; 0x8];
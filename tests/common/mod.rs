@@ -0,0 +1,82 @@
+// Not every test file uses every helper
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+pub fn output_dir(test_name: &str) -> PathBuf {
+    let path = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(test_name);
+    std::fs::create_dir_all(&path).unwrap();
+    path
+}
+
+/// Links the `link.x` in `dir` with rust-lld, the default linker of the embedded Rust targets,
+/// and returns the VMA and LMA of each output section, read from the link map. The input sections
+/// are `.text` and the given sections of `fixture.rs`.
+pub fn link(dir: &Path, sections: &[&str]) -> HashMap<String, (u64, u64)> {
+    let rustc = std::env::var_os("RUSTC").unwrap_or_else(|| "rustc".into());
+    let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/common/fixture.rs");
+    let object = dir.join("fixture.o");
+    run(Command::new(&rustc)
+        .args([
+            "--crate-type",
+            "lib",
+            "--emit",
+            "obj",
+            "-C",
+            "panic=abort",
+            "-o",
+        ])
+        .arg(&object)
+        .args(sections.iter().flat_map(|section| ["--cfg", section]))
+        .arg(fixture));
+
+    // rust-lld ships with the toolchain, next to the tools of the host target
+    let version = run(Command::new(&rustc).arg("-vV"));
+    let host = version
+        .lines()
+        .find_map(|line| line.strip_prefix("host: "))
+        .unwrap();
+    let sysroot = run(Command::new(&rustc).args(["--print", "sysroot"]));
+    let lld = Path::new(sysroot.trim())
+        .join("lib/rustlib")
+        .join(host)
+        .join("bin/rust-lld");
+    let map = dir.join("link.map");
+    run(Command::new(lld)
+        .args(["-flavor", "gnu", "-T"])
+        .arg(dir.join("link.x"))
+        .arg(&object)
+        .arg("-o")
+        .arg(dir.join("link.elf"))
+        .arg(format!("-Map={}", map.display())));
+
+    // Output sections start at the `Out` column, input sections and symbols are indented further
+    std::fs::read_to_string(map)
+        .unwrap()
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let (addresses, name) = line.split_at_checked(49)?;
+            if name.starts_with(' ') || name.contains(' ') {
+                return None;
+            }
+            let mut addresses = addresses.split_whitespace();
+            let vma = u64::from_str_radix(addresses.next()?, 16).ok()?;
+            let lma = u64::from_str_radix(addresses.next()?, 16).ok()?;
+            Some((name.to_owned(), (vma, lma)))
+        })
+        .collect()
+}
+
+fn run(command: &mut Command) -> String {
+    let output = command.output().unwrap();
+    assert!(
+        output.status.success(),
+        "{:?} failed:\n{}",
+        command,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).unwrap()
+}
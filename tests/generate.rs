@@ -0,0 +1,473 @@
+use ld_script::{Address, AddressWidth, MemoryLayout, StackPlacement, U32Ext};
+
+mod common;
+
+use common::output_dir;
+use std::path::Path;
+
+fn generated_script(test_name: &str, layout: MemoryLayout) -> String {
+    let path = output_dir(test_name);
+    layout.generate(&path).unwrap();
+    std::fs::read_to_string(path.join("link.x")).unwrap()
+}
+
+// Type checks generated reset code as a `no_std` library for the host, with warnings denied. The
+// operands of its assembly fill a register on 32-bit ARM, unlike on the host.
+fn check_reset(test_name: &str, golden: &str, edition: &str) {
+    let path = output_dir(test_name);
+    let golden = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden")
+        .join(golden);
+    let crate_root = path.join("lib.rs");
+    std::fs::write(
+        &crate_root,
+        format!("#![no_std]\ninclude!({:?});\n", golden.display()),
+    )
+    .unwrap();
+
+    let rustc = std::env::var_os("RUSTC").unwrap_or_else(|| "rustc".into());
+    let output = std::process::Command::new(rustc)
+        .args([
+            "--crate-type",
+            "lib",
+            "--emit",
+            "metadata",
+            "-D",
+            "warnings",
+            "-A",
+            "asm_sub_register",
+        ])
+        .args(["--edition", edition, "--out-dir"])
+        .arg(&path)
+        .arg(&crate_root)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{} does not compile on edition {}:\n{}",
+        golden.display(),
+        edition,
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn memory_block_lists_all_regions() {
+    let mut layout = MemoryLayout::new().unwrap();
+    layout
+        .add_rx_region("flash", Address::new(0x08000000), 512.kilobytes())
+        .unwrap();
+    layout
+        .add_rw_region("sram", Address::new(0x20000000), 128.kilobytes())
+        .unwrap();
+    layout
+        .add_rwx_region("ccram", Address::new(0x10000000), 64.kilobytes())
+        .unwrap();
+
+    assert_eq!(
+        generated_script("memory_block_lists_all_regions", layout),
+        include_str!("golden/memory_block.x")
+    );
+}
+
+#[test]
+fn memory_block_without_regions() {
+    let layout = MemoryLayout::new().unwrap();
+
+    assert_eq!(
+        generated_script("memory_block_without_regions", layout),
+        include_str!("golden/empty_memory_block.x")
+    );
+}
+
+#[test]
+fn memory_block_with_64_bit_addresses() {
+    let mut layout = MemoryLayout::with_address_width(AddressWidth::Bits64).unwrap();
+    let ddr = layout
+        .add_rwx_region("ddr", Address::new(0x8_0000_0000), 2.gigabytes())
+        .unwrap();
+    layout
+        .add_rwx_region("ocram", Address::new(0xfffc0000), 256.kilobytes())
+        .unwrap();
+    layout.text(&ddr, &ddr, Some(1.megabytes())).unwrap();
+
+    assert_eq!(
+        generated_script("memory_block_with_64_bit_addresses", layout),
+        include_str!("golden/memory_block_64.x")
+    );
+}
+
+#[test]
+fn sections_block_places_sections() {
+    let mut layout = MemoryLayout::new().unwrap();
+    let flash = layout
+        .add_rx_region("flash", Address::new(0x08000000), 512.kilobytes())
+        .unwrap();
+    let ram = layout
+        .add_rwx_region("ram", Address::new(0x20000000), 128.kilobytes())
+        .unwrap();
+
+    layout.custom_section("config", &ram, &flash, None).unwrap();
+    layout.stack(&ram, Some(4.kilobytes())).unwrap();
+    layout.uninit(&ram, None).unwrap();
+    layout.bss(&ram, None).unwrap();
+    layout.data(&ram, &flash, None).unwrap();
+    layout.ramfunc(&ram, &flash, None).unwrap();
+    layout.rodata(&flash, &flash, None).unwrap();
+    layout.text(&flash, &flash, None).unwrap();
+    layout.vector_table(&flash, &flash, None).unwrap();
+
+    assert_eq!(
+        generated_script("sections_block_places_sections", layout),
+        include_str!("golden/sections_block.x")
+    );
+
+    let sections = common::link(
+        &output_dir("sections_block_places_sections"),
+        &["vector_table", "ramfunc", "data", "config"],
+    );
+    assert_eq!(sections[".vector_table"], (0x08000000, 0x08000000));
+    assert_eq!(sections[".text"], (0x08000010, 0x08000010));
+    assert_eq!(sections[".ramfunc"], (0x20000000, 0x0800007c));
+    assert_eq!(sections[".data"], (0x20000020, 0x0800009c));
+    assert_eq!(sections[".bss"], (0x20000030, 0x20000030));
+    assert_eq!(sections[".uninit"], (0x20000030, 0x20000030));
+    assert_eq!(sections[".config"], (0x20000038, 0x080000ac));
+    assert_eq!(sections[".stack"], (0x2001f000, 0x2001f000));
+}
+
+#[test]
+fn tables_list_sections_of_every_ram() {
+    let mut layout = MemoryLayout::new().unwrap();
+    let flash = layout
+        .add_rx_region("flash", Address::new(0x08000000), 1.megabytes())
+        .unwrap();
+    let dtcm = layout
+        .add_rw_region("dtcm", Address::new(0x20000000), 128.kilobytes())
+        .unwrap();
+    let sram = layout
+        .add_rw_region("sram", Address::new(0x30000000), 256.kilobytes())
+        .unwrap();
+
+    layout.text(&flash, &flash, None).unwrap();
+    layout.data(&dtcm, &flash, None).unwrap();
+    layout.named_data("sram_data", &sram, &flash, None).unwrap();
+    layout.bss(&dtcm, None).unwrap();
+    layout.named_bss("sram_bss", &sram, None).unwrap();
+
+    assert_eq!(
+        generated_script("tables_list_sections_of_every_ram", layout),
+        include_str!("golden/init_tables.x")
+    );
+}
+
+#[test]
+fn tables_without_text() {
+    let mut layout = MemoryLayout::new().unwrap();
+    let flash = layout
+        .add_rx_region("flash", Address::new(0x08000000), 512.kilobytes())
+        .unwrap();
+    let ram = layout
+        .add_rw_region("ram", Address::new(0x20000000), 128.kilobytes())
+        .unwrap();
+
+    layout.rodata(&flash, &flash, None).unwrap();
+    layout.data(&ram, &flash, None).unwrap();
+    layout.bss(&ram, None).unwrap();
+
+    assert_eq!(
+        generated_script("tables_without_text", layout),
+        include_str!("golden/init_tables_without_text.x")
+    );
+}
+
+#[test]
+fn tables_are_loaded_with_rodata_in_ram() {
+    let mut layout = MemoryLayout::new().unwrap();
+    let flash = layout
+        .add_rx_region("flash", Address::new(0x08000000), 512.kilobytes())
+        .unwrap();
+    let ram = layout
+        .add_rw_region("ram", Address::new(0x20000000), 128.kilobytes())
+        .unwrap();
+
+    layout.rodata(&ram, &flash, None).unwrap();
+    layout.bss(&ram, None).unwrap();
+
+    assert_eq!(
+        generated_script("tables_are_loaded_with_rodata_in_ram", layout),
+        include_str!("golden/init_tables_rodata_in_ram.x")
+    );
+}
+
+#[test]
+fn sections_after_a_fixed_offset_follow_it() {
+    let mut layout = MemoryLayout::new().unwrap();
+    let flash = layout
+        .add_rx_region("flash", Address::new(0x08000000), 64.kilobytes())
+        .unwrap();
+
+    layout.vector_table(&flash, &flash, None).unwrap();
+    layout.text(&flash, &flash, None).unwrap();
+    layout
+        .custom_section("trailer", &flash, &flash, Some(0x100.bytes()))
+        .unwrap();
+    layout
+        .set_section_offset("vector_table", 0.bytes())
+        .unwrap();
+    layout
+        .set_section_offset("trailer", 0xff00.bytes())
+        .unwrap();
+
+    let path = output_dir("sections_after_a_fixed_offset_follow_it");
+    layout.generate(&path).unwrap();
+    let sections = common::link(&path, &["vector_table"]);
+    assert_eq!(sections[".vector_table"], (0x08000000, 0x08000000));
+    assert_eq!(sections[".text"], (0x08000010, 0x08000010));
+    assert_eq!(sections[".trailer"], (0x0800ff00, 0x0800ff00));
+}
+
+#[test]
+fn reset_initializes_memory() {
+    let mut layout = MemoryLayout::new().unwrap();
+    let flash = layout
+        .add_rx_region("flash", Address::new(0x08000000), 512.kilobytes())
+        .unwrap();
+    let ram = layout
+        .add_rwx_region("ram", Address::new(0x20000000), 128.kilobytes())
+        .unwrap();
+
+    layout.text(&flash, &flash, None).unwrap();
+    layout.ramfunc(&ram, &flash, None).unwrap();
+    layout.data(&ram, &flash, None).unwrap();
+    layout.bss(&ram, None).unwrap();
+
+    let path = output_dir("reset_initializes_memory");
+    layout.generate_reset(&path).unwrap();
+    assert_eq!(
+        std::fs::read_to_string(path.join("reset.rs")).unwrap(),
+        include_str!("golden/reset.rs")
+    );
+}
+
+#[test]
+fn section_budgets_are_asserted() {
+    let mut layout = MemoryLayout::new().unwrap();
+    let flash = layout
+        .add_rx_region("flash", Address::new(0x08000000), 512.kilobytes())
+        .unwrap();
+    let ram = layout
+        .add_rwx_region("ram", Address::new(0x20000000), 128.kilobytes())
+        .unwrap();
+
+    layout.text(&flash, &flash, Some(256.kilobytes())).unwrap();
+    layout.data(&ram, &flash, Some(16.kilobytes())).unwrap();
+    layout.bss(&ram, None).unwrap();
+
+    assert_eq!(
+        generated_script("section_budgets_are_asserted", layout),
+        include_str!("golden/section_budgets.x")
+    );
+}
+
+#[test]
+fn section_alignment_is_emitted() {
+    let mut layout = MemoryLayout::new().unwrap();
+    let flash = layout
+        .add_rx_region("flash", Address::new(0x08000000), 512.kilobytes())
+        .unwrap();
+    let ram = layout
+        .add_rwx_region("ram", Address::new(0x20000000), 128.kilobytes())
+        .unwrap();
+
+    layout
+        .vector_table(&flash, &flash, Some(1.kilobytes()))
+        .unwrap();
+    layout.text(&flash, &flash, None).unwrap();
+    layout.bss(&ram, None).unwrap();
+    layout.custom_section("dma", &ram, &ram, None).unwrap();
+    layout
+        .set_section_offset("vector_table", 0.bytes())
+        .unwrap();
+    layout
+        .set_section_alignment("vector_table", 1.kilobytes())
+        .unwrap();
+    layout.set_section_alignment("bss", 8.bytes()).unwrap();
+    layout.set_section_alignment("dma", 32.bytes()).unwrap();
+
+    assert_eq!(
+        generated_script("section_alignment_is_emitted", layout),
+        include_str!("golden/section_alignment.x")
+    );
+}
+
+#[test]
+fn heap_fills_remaining_space() {
+    let mut layout = MemoryLayout::new().unwrap();
+    let flash = layout
+        .add_rx_region("flash", Address::new(0x08000000), 512.kilobytes())
+        .unwrap();
+    let ram = layout
+        .add_rwx_region("ram", Address::new(0x20000000), 128.kilobytes())
+        .unwrap();
+
+    layout.text(&flash, &flash, None).unwrap();
+    layout.bss(&ram, None).unwrap();
+    layout.heap(&ram, None).unwrap();
+    layout.stack(&ram, Some(4.kilobytes())).unwrap();
+
+    let path = output_dir("heap_fills_remaining_space");
+    layout.generate(&path).unwrap();
+    layout.generate_reset(&path).unwrap();
+    let script = std::fs::read_to_string(path.join("link.x")).unwrap();
+    assert!(script.contains("PROVIDE(_sheap = __sheap);"));
+    assert!(script.contains("PROVIDE(_eheap = __eheap);"));
+    assert_eq!(script, include_str!("golden/heap.x"));
+    assert_eq!(
+        std::fs::read_to_string(path.join("reset.rs")).unwrap(),
+        include_str!("golden/reset_heap.rs")
+    );
+}
+
+#[test]
+fn stack_at_bottom_of_ram() {
+    let mut layout = MemoryLayout::new().unwrap();
+    let flash = layout
+        .add_rx_region("flash", Address::new(0x08000000), 512.kilobytes())
+        .unwrap();
+    let ram = layout
+        .add_rwx_region("ram", Address::new(0x20000000), 128.kilobytes())
+        .unwrap();
+
+    layout.set_stack_placement(StackPlacement::Bottom);
+    layout.text(&flash, &flash, None).unwrap();
+    layout.data(&ram, &flash, None).unwrap();
+    layout.bss(&ram, None).unwrap();
+    layout.stack(&ram, Some(4.kilobytes())).unwrap();
+
+    assert_eq!(
+        generated_script("stack_at_bottom_of_ram", layout),
+        include_str!("golden/stack_bottom.x")
+    );
+}
+
+#[test]
+fn stacks_with_guards() {
+    let mut layout = MemoryLayout::new().unwrap();
+    let flash = layout
+        .add_rx_region("flash", Address::new(0x08000000), 512.kilobytes())
+        .unwrap();
+    let ram = layout
+        .add_rwx_region("ram", Address::new(0x20000000), 128.kilobytes())
+        .unwrap();
+    let sram4 = layout
+        .add_rw_region("sram4", Address::new(0x38000000), 64.kilobytes())
+        .unwrap();
+
+    layout.text(&flash, &flash, None).unwrap();
+    layout.bss(&ram, None).unwrap();
+    layout.stack(&ram, Some(2.kilobytes())).unwrap();
+    layout
+        .named_stack("psp_stack", &ram, Some(8.kilobytes()))
+        .unwrap();
+    layout
+        .named_stack("core1_stack", &sram4, Some(4.kilobytes()))
+        .unwrap();
+    layout.set_stack_guard("stack", 256.bytes()).unwrap();
+    layout.set_stack_guard("psp_stack", 256.bytes()).unwrap();
+
+    assert_eq!(
+        generated_script("stacks_with_guards", layout),
+        include_str!("golden/stacks_with_guards.x")
+    );
+
+    let sections = common::link(&output_dir("stacks_with_guards"), &[]);
+    assert_eq!(sections[".stack_guard"], (0x2001d600, 0x2001d600));
+    assert_eq!(sections[".stack"], (0x2001d700, 0x2001d700));
+    assert_eq!(sections[".psp_stack_guard"], (0x2001df00, 0x2001df00));
+    assert_eq!(sections[".psp_stack"], (0x2001e000, 0x2001e000));
+    assert_eq!(sections[".core1_stack"], (0x3800f000, 0x3800f000));
+}
+
+#[test]
+fn reset_leaves_uninit_alone() {
+    let mut layout = MemoryLayout::new().unwrap();
+    let flash = layout
+        .add_rx_region("flash", Address::new(0x08000000), 512.kilobytes())
+        .unwrap();
+    let ram = layout
+        .add_rwx_region("ram", Address::new(0x20000000), 128.kilobytes())
+        .unwrap();
+
+    layout.text(&flash, &flash, None).unwrap();
+    layout.bss(&ram, None).unwrap();
+    layout.uninit(&ram, Some(1.kilobytes())).unwrap();
+
+    let path = output_dir("reset_leaves_uninit_alone");
+    layout.generate_reset(&path).unwrap();
+    assert_eq!(
+        std::fs::read_to_string(path.join("reset.rs")).unwrap(),
+        include_str!("golden/reset_uninit.rs")
+    );
+}
+
+#[test]
+fn reset_paints_stacks() {
+    let mut layout = MemoryLayout::new().unwrap();
+    let flash = layout
+        .add_rx_region("flash", Address::new(0x08000000), 512.kilobytes())
+        .unwrap();
+    let ram = layout
+        .add_rwx_region("ram", Address::new(0x20000000), 128.kilobytes())
+        .unwrap();
+
+    layout.text(&flash, &flash, None).unwrap();
+    layout.bss(&ram, None).unwrap();
+    layout.stack(&ram, Some(2.kilobytes())).unwrap();
+    layout
+        .named_stack("psp_stack", &ram, Some(8.kilobytes()))
+        .unwrap();
+    layout.set_stack_painting("stack", true).unwrap();
+    layout.set_stack_painting("psp_stack", true).unwrap();
+
+    let path = output_dir("reset_paints_stacks");
+    layout.generate_reset(&path).unwrap();
+    assert_eq!(
+        std::fs::read_to_string(path.join("reset.rs")).unwrap(),
+        include_str!("golden/reset_painted.rs")
+    );
+}
+
+#[test]
+fn errors_convert_into_boxed_errors() {
+    fn generate(path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        let mut layout = MemoryLayout::new()?;
+        layout.add_rx_region("flash", Address::new(0x08000000), 512.kilobytes())?;
+        layout.generate(path)?;
+        Ok(())
+    }
+
+    let path = output_dir("errors_convert_into_boxed_errors").join("missing");
+    let error = generate(&path).unwrap_err();
+    assert!(error.to_string().starts_with(&format!(
+        "failed to write `{}`",
+        path.join("link.x").display()
+    )));
+    assert!(error.source().is_some());
+}
+
+// The painted reset code reads the stack pointer with Arm assembly, so it is left out
+#[test]
+fn reset_compiles_on_every_edition() {
+    for golden in [
+        "reset.rs",
+        "reset_heap.rs",
+        "reset_uninit.rs",
+        "reset_painted.rs",
+    ] {
+        for edition in ["2018", "2021", "2024"] {
+            let test_name = format!("reset_compiles_{}_{}", golden.replace('.', "_"), edition);
+            check_reset(&test_name, golden, edition);
+        }
+    }
+}
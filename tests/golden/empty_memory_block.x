@@ -0,0 +1,11 @@
+/* Generated by ld_script. Do not edit. */
+
+ENTRY(Reset);
+
+MEMORY
+{
+}
+
+SECTIONS
+{
+}
@@ -0,0 +1,53 @@
+/* Generated by ld_script. Do not edit. */
+
+ENTRY(Reset);
+
+MEMORY
+{
+    flash (rx) : ORIGIN = 0x08000000, LENGTH = 0x00080000
+    ram (rwx) : ORIGIN = 0x20000000, LENGTH = 0x00020000
+}
+
+SECTIONS
+{
+    .text :
+    {
+        __stext = .;
+        *(.text .text.*)
+        __etext = .;
+    } > flash
+
+    .zero_table : ALIGN(4)
+    {
+        __szero_table = .;
+        LONG(__sbss);
+        LONG(__ebss);
+        __ezero_table = .;
+    } > flash
+
+    .bss (NOLOAD) : ALIGN(4)
+    {
+        __sbss = .;
+        *(.bss .bss.* COMMON)
+        . = ALIGN(4);
+        __ebss = .;
+    } > ram
+
+    .heap (NOLOAD) : ALIGN(8)
+    {
+        __sheap = .;
+        . = ORIGIN(ram) + LENGTH(ram) - 0x1000;
+        __eheap = .;
+    } > ram
+    PROVIDE(_sheap = __sheap);
+    PROVIDE(_eheap = __eheap);
+
+    .stack ORIGIN(ram) + LENGTH(ram) - 0x1000 (NOLOAD) : ALIGN(8)
+    {
+        __sstack = .;
+        . += 0x1000;
+        __estack = .;
+    } > ram
+    _stack_start = __estack;
+    ASSERT(SIZEOF(.stack) <= 0x1000, ".stack exceeds its budget of 4 KiB")
+}
@@ -0,0 +1,76 @@
+/* Generated by ld_script. Do not edit. */
+
+ENTRY(Reset);
+
+MEMORY
+{
+    flash (rx) : ORIGIN = 0x08000000, LENGTH = 0x00100000
+    dtcm (rw) : ORIGIN = 0x20000000, LENGTH = 0x00020000
+    sram (rw) : ORIGIN = 0x30000000, LENGTH = 0x00040000
+}
+
+SECTIONS
+{
+    .text :
+    {
+        __stext = .;
+        *(.text .text.*)
+        __etext = .;
+    } > flash
+
+    .copy_table : ALIGN(4)
+    {
+        __scopy_table = .;
+        LONG(__sidata);
+        LONG(__sdata);
+        LONG(__edata);
+        LONG(__sisram_data);
+        LONG(__ssram_data);
+        LONG(__esram_data);
+        __ecopy_table = .;
+    } > flash
+
+    .zero_table : ALIGN(4)
+    {
+        __szero_table = .;
+        LONG(__sbss);
+        LONG(__ebss);
+        LONG(__ssram_bss);
+        LONG(__esram_bss);
+        __ezero_table = .;
+    } > flash
+
+    .data : ALIGN(4)
+    {
+        __sdata = .;
+        *(.data .data.*)
+        . = ALIGN(4);
+        __edata = .;
+    } > dtcm AT> flash
+    __sidata = LOADADDR(.data);
+
+    .sram_data : ALIGN(4)
+    {
+        __ssram_data = .;
+        *(.sram_data .sram_data.*)
+        . = ALIGN(4);
+        __esram_data = .;
+    } > sram AT> flash
+    __sisram_data = LOADADDR(.sram_data);
+
+    .bss (NOLOAD) : ALIGN(4)
+    {
+        __sbss = .;
+        *(.bss .bss.* COMMON)
+        . = ALIGN(4);
+        __ebss = .;
+    } > dtcm
+
+    .sram_bss (NOLOAD) : ALIGN(4)
+    {
+        __ssram_bss = .;
+        *(.sram_bss .sram_bss.*)
+        . = ALIGN(4);
+        __esram_bss = .;
+    } > sram
+}
@@ -0,0 +1,46 @@
+/* Generated by ld_script. Do not edit. */
+
+ENTRY(Reset);
+
+MEMORY
+{
+    flash (rx) : ORIGIN = 0x08000000, LENGTH = 0x00080000
+    ram (rw) : ORIGIN = 0x20000000, LENGTH = 0x00020000
+}
+
+SECTIONS
+{
+    .rodata : ALIGN(4)
+    {
+        __srodata = .;
+        *(.rodata .rodata.*)
+        . = ALIGN(4);
+        __erodata = .;
+    } > ram AT> flash
+    __sirodata = LOADADDR(.rodata);
+
+    .copy_table : ALIGN(4)
+    {
+        __scopy_table = .;
+        LONG(__sirodata);
+        LONG(__srodata);
+        LONG(__erodata);
+        __ecopy_table = .;
+    } > flash
+
+    .zero_table : ALIGN(4)
+    {
+        __szero_table = .;
+        LONG(__sbss);
+        LONG(__ebss);
+        __ezero_table = .;
+    } > flash
+
+    .bss (NOLOAD) : ALIGN(4)
+    {
+        __sbss = .;
+        *(.bss .bss.* COMMON)
+        . = ALIGN(4);
+        __ebss = .;
+    } > ram
+}
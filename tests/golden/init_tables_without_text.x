@@ -0,0 +1,53 @@
+/* Generated by ld_script. Do not edit. */
+
+ENTRY(Reset);
+
+MEMORY
+{
+    flash (rx) : ORIGIN = 0x08000000, LENGTH = 0x00080000
+    ram (rw) : ORIGIN = 0x20000000, LENGTH = 0x00020000
+}
+
+SECTIONS
+{
+    .rodata :
+    {
+        __srodata = .;
+        *(.rodata .rodata.*)
+        __erodata = .;
+    } > flash
+
+    .copy_table : ALIGN(4)
+    {
+        __scopy_table = .;
+        LONG(__sidata);
+        LONG(__sdata);
+        LONG(__edata);
+        __ecopy_table = .;
+    } > flash
+
+    .zero_table : ALIGN(4)
+    {
+        __szero_table = .;
+        LONG(__sbss);
+        LONG(__ebss);
+        __ezero_table = .;
+    } > flash
+
+    .data : ALIGN(4)
+    {
+        __sdata = .;
+        *(.data .data.*)
+        . = ALIGN(4);
+        __edata = .;
+    } > ram AT> flash
+    __sidata = LOADADDR(.data);
+
+    .bss (NOLOAD) : ALIGN(4)
+    {
+        __sbss = .;
+        *(.bss .bss.* COMMON)
+        . = ALIGN(4);
+        __ebss = .;
+    } > ram
+}
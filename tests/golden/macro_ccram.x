@@ -0,0 +1,53 @@
+/* Generated by ld_script. Do not edit. */
+
+ENTRY(Reset);
+
+MEMORY
+{
+    flash (rx) : ORIGIN = 0x08000000, LENGTH = 0x00080000
+    ccram (rw) : ORIGIN = 0x10000000, LENGTH = 0x00010000
+}
+
+SECTIONS
+{
+    .text :
+    {
+        __stext = .;
+        *(.text .text.*)
+        __etext = .;
+    } > flash
+
+    .copy_table : ALIGN(4)
+    {
+        __scopy_table = .;
+        LONG(__siccramdata);
+        LONG(__sccramdata);
+        LONG(__eccramdata);
+        __ecopy_table = .;
+    } > flash
+
+    .zero_table : ALIGN(4)
+    {
+        __szero_table = .;
+        LONG(__sccrambss);
+        LONG(__eccrambss);
+        __ezero_table = .;
+    } > flash
+
+    .ccramdata : ALIGN(4)
+    {
+        __sccramdata = .;
+        *(.ccramdata .ccramdata.*)
+        . = ALIGN(4);
+        __eccramdata = .;
+    } > ccram AT> flash
+    __siccramdata = LOADADDR(.ccramdata);
+
+    .ccrambss (NOLOAD) : ALIGN(4)
+    {
+        __sccrambss = .;
+        *(.ccrambss .ccrambss.*)
+        . = ALIGN(4);
+        __eccrambss = .;
+    } > ccram
+}
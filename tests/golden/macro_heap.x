@@ -0,0 +1,38 @@
+/* Generated by ld_script. Do not edit. */
+
+ENTRY(Reset);
+
+MEMORY
+{
+    flash (rx) : ORIGIN = 0x08000000, LENGTH = 0x00080000
+    ram (rw) : ORIGIN = 0x20000000, LENGTH = 0x00020000
+}
+
+SECTIONS
+{
+    .bss (NOLOAD) : ALIGN(4)
+    {
+        __sbss = .;
+        *(.bss .bss.* COMMON)
+        . = ALIGN(4);
+        __ebss = .;
+    } > ram
+
+    .zero_table : ALIGN(4)
+    {
+        __szero_table = .;
+        LONG(__sbss);
+        LONG(__ebss);
+        __ezero_table = .;
+    } > ram
+
+    .heap (NOLOAD) : ALIGN(8)
+    {
+        __sheap = .;
+        . += 0x4000;
+        __eheap = .;
+    } > ram
+    PROVIDE(_sheap = __sheap);
+    PROVIDE(_eheap = __eheap);
+    ASSERT(SIZEOF(.heap) <= 0x4000, ".heap exceeds its budget of 16 KiB")
+}
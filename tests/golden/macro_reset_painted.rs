@@ -0,0 +1,68 @@
+// Generated by ld_script. Do not edit.
+
+/// Entry point of the firmware. Initializes memory and calls `main`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn Reset() -> ! {
+    unsafe extern "C" {
+        static mut __sstack: u32;
+        static mut _stack_start: u32;
+    }
+
+    unsafe extern "Rust" {
+        fn main() -> !;
+    }
+
+
+    let sp: *mut u32;
+    unsafe { ::core::arch::asm!("mov {}, sp", out(reg) sp) };
+
+    macro_rules! paint {
+        ($start:expr, $end:expr) => {
+            let start: *mut u32 = $start;
+            let end: *mut u32 = $end;
+            // The stack in use is only painted up to the stack pointer
+            let end = if start <= sp && sp < end { sp } else { end };
+            unsafe {
+                ::core::arch::asm!(
+                    "2:",
+                    "cmp {word}, {end}",
+                    "bhs 3f",
+                    "str {paint}, [{word}]",
+                    "adds {word}, #4",
+                    "b 2b",
+                    "3:",
+                    word = inout(reg) start => _,
+                    end = in(reg) end,
+                    paint = in(reg) STACK_PAINT,
+                    options(nostack),
+                )
+            };
+        };
+    }
+
+    paint!(::core::ptr::addr_of_mut!(__sstack), ::core::ptr::addr_of_mut!(_stack_start));
+    unsafe { main() }
+}
+
+/// The word the stacks are painted with by `Reset`.
+pub const STACK_PAINT: u32 = 0xdeadbeef;
+
+/// The most stack space used since reset in bytes, found by scanning the stack from the bottom
+/// for the first word that is no longer painted.
+unsafe fn high_water_mark(start: *const u32, end: *const u32) -> usize {
+    let mut word = start;
+    while word < end && unsafe { ::core::ptr::read_volatile(word) } == STACK_PAINT {
+        word = unsafe { word.add(1) };
+    }
+    end as usize - word as usize
+}
+
+/// The high-water mark of `.stack` in bytes.
+pub fn stack_high_water_mark() -> usize {
+    unsafe extern "C" {
+        static __sstack: u32;
+        static _stack_start: u32;
+    }
+
+    unsafe { high_water_mark(::core::ptr::addr_of!(__sstack), ::core::ptr::addr_of!(_stack_start)) }
+}
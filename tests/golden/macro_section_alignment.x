@@ -0,0 +1,37 @@
+/* Generated by ld_script. Do not edit. */
+
+ENTRY(Reset);
+
+MEMORY
+{
+    flash (rx) : ORIGIN = 0x08000000, LENGTH = 0x00080000
+    ram (rw) : ORIGIN = 0x20000000, LENGTH = 0x00020000
+}
+
+SECTIONS
+{
+    .vector_table ORIGIN(flash) + 0x0 : ALIGN(512)
+    {
+        __svector_table = .;
+        KEEP(*(.vector_table .vector_table.*))
+        . = ALIGN(512);
+        __evector_table = .;
+    } > flash
+    ASSERT(SIZEOF(.vector_table) <= 0x200, ".vector_table exceeds its budget of 512 bytes")
+
+    .zero_table : ALIGN(4)
+    {
+        __szero_table = .;
+        LONG(__sbss);
+        LONG(__ebss);
+        __ezero_table = .;
+    } > flash
+
+    .bss (NOLOAD) : ALIGN(32)
+    {
+        __sbss = .;
+        *(.bss .bss.* COMMON)
+        . = ALIGN(32);
+        __ebss = .;
+    } > ram
+}
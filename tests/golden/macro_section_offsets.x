@@ -0,0 +1,26 @@
+/* Generated by ld_script. Do not edit. */
+
+ENTRY(Reset);
+
+MEMORY
+{
+    flash (rx) : ORIGIN = 0x08000000, LENGTH = 0x00080000
+}
+
+SECTIONS
+{
+    .vector_table ORIGIN(flash) + 0x0 :
+    {
+        __svector_table = .;
+        KEEP(*(.vector_table .vector_table.*))
+        __evector_table = .;
+    } > flash
+    ASSERT(SIZEOF(.vector_table) <= 0x200, ".vector_table exceeds its budget of 512 bytes")
+
+    .text ORIGIN(flash) + 0x400 :
+    {
+        __stext = .;
+        *(.text .text.*)
+        __etext = .;
+    } > flash
+}
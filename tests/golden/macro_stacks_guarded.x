@@ -0,0 +1,51 @@
+/* Generated by ld_script. Do not edit. */
+
+ENTRY(Reset);
+
+MEMORY
+{
+    flash (rx) : ORIGIN = 0x08000000, LENGTH = 0x00080000
+    ram (rw) : ORIGIN = 0x20000000, LENGTH = 0x00020000
+}
+
+SECTIONS
+{
+    .text :
+    {
+        __stext = .;
+        *(.text .text.*)
+        __etext = .;
+    } > flash
+
+    .stack_guard ORIGIN(ram) + LENGTH(ram) - 0x1980 (NOLOAD) :
+    {
+        __sstack_guard = .;
+        . += 0x100;
+        __estack_guard = .;
+    } > ram
+
+    .stack ORIGIN(ram) + LENGTH(ram) - 0x1880 (NOLOAD) : ALIGN(8)
+    {
+        __sstack = .;
+        . += 0x1000;
+        __estack = .;
+    } > ram
+    _stack_start = __estack;
+    ASSERT(SIZEOF(.stack) <= 0x1000, ".stack exceeds its budget of 4 KiB")
+
+    .core1_stack_guard ORIGIN(ram) + LENGTH(ram) - 0x880 (NOLOAD) :
+    {
+        __score1_stack_guard = .;
+        . += 0x80;
+        __ecore1_stack_guard = .;
+    } > ram
+
+    .core1_stack ORIGIN(ram) + LENGTH(ram) - 0x800 (NOLOAD) : ALIGN(8)
+    {
+        __score1_stack = .;
+        . += 0x800;
+        __ecore1_stack = .;
+    } > ram
+    _core1_stack_start = __ecore1_stack;
+    ASSERT(SIZEOF(.core1_stack) <= 0x800, ".core1_stack exceeds its budget of 2 KiB")
+}
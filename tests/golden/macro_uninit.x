@@ -0,0 +1,37 @@
+/* Generated by ld_script. Do not edit. */
+
+ENTRY(Reset);
+
+MEMORY
+{
+    ram (rw) : ORIGIN = 0x20000000, LENGTH = 0x00020000
+}
+
+SECTIONS
+{
+    .bss (NOLOAD) : ALIGN(4)
+    {
+        __sbss = .;
+        *(.bss .bss.* COMMON)
+        . = ALIGN(4);
+        __ebss = .;
+    } > ram
+
+    .zero_table : ALIGN(4)
+    {
+        __szero_table = .;
+        LONG(__sbss);
+        LONG(__ebss);
+        __ezero_table = .;
+    } > ram
+
+    .uninit (NOLOAD) : ALIGN(4)
+    {
+        __suninit = .;
+        . += 0x8;
+        *(.uninit .uninit.*)
+        . = ALIGN(4);
+        __euninit = .;
+    } > ram
+    ASSERT(SIZEOF(.uninit) <= 0x400, ".uninit exceeds its budget of 1 KiB")
+}
@@ -0,0 +1,14 @@
+/* Generated by ld_script. Do not edit. */
+
+ENTRY(Reset);
+
+MEMORY
+{
+    flash (rx) : ORIGIN = 0x08000000, LENGTH = 0x00080000
+    sram (rw) : ORIGIN = 0x20000000, LENGTH = 0x00020000
+    ccram (rwx) : ORIGIN = 0x10000000, LENGTH = 0x00010000
+}
+
+SECTIONS
+{
+}
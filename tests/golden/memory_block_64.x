@@ -0,0 +1,20 @@
+/* Generated by ld_script. Do not edit. */
+
+ENTRY(Reset);
+
+MEMORY
+{
+    ddr (rwx) : ORIGIN = 0x800000000, LENGTH = 0x80000000
+    ocram (rwx) : ORIGIN = 0xfffc0000, LENGTH = 0x00040000
+}
+
+SECTIONS
+{
+    .text :
+    {
+        __stext = .;
+        *(.text .text.*)
+        __etext = .;
+    } > ddr
+    ASSERT(SIZEOF(.text) <= 0x100000, ".text exceeds its budget of 1 MiB")
+}
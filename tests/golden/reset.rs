@@ -0,0 +1,53 @@
+// Generated by ld_script. Do not edit.
+
+/// Entry point of the firmware. Initializes memory and calls `main`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn Reset() -> ! {
+    unsafe extern "C" {
+        static __scopy_table: [*mut u32; 3];
+        static __ecopy_table: [*mut u32; 3];
+        static __szero_table: [*mut u32; 2];
+        static __ezero_table: [*mut u32; 2];
+    }
+
+    unsafe extern "Rust" {
+        fn main() -> !;
+    }
+
+    unsafe fn copy(mut src: *const u32, mut dst: *mut u32, end: *mut u32) {
+        while dst < end {
+            unsafe {
+                ::core::ptr::write_volatile(dst, ::core::ptr::read(src));
+                dst = dst.add(1);
+                src = src.add(1);
+            }
+        }
+    }
+
+    unsafe fn zero(mut dst: *mut u32, end: *mut u32) {
+        while dst < end {
+            unsafe {
+                ::core::ptr::write_volatile(dst, 0);
+                dst = dst.add(1);
+            }
+        }
+    }
+
+    let mut record = ::core::ptr::addr_of!(__scopy_table);
+    while record < ::core::ptr::addr_of!(__ecopy_table) {
+        unsafe {
+            let [load, start, end] = *record;
+            copy(load, start, end);
+            record = record.add(1);
+        }
+    }
+    let mut record = ::core::ptr::addr_of!(__szero_table);
+    while record < ::core::ptr::addr_of!(__ezero_table) {
+        unsafe {
+            let [start, end] = *record;
+            zero(start, end);
+            record = record.add(1);
+        }
+    }
+    unsafe { main() }
+}
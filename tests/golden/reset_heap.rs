@@ -0,0 +1,49 @@
+// Generated by ld_script. Do not edit.
+
+/// Entry point of the firmware. Initializes memory and calls `main`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn Reset() -> ! {
+    unsafe extern "C" {
+        static __szero_table: [*mut u32; 2];
+        static __ezero_table: [*mut u32; 2];
+    }
+
+    unsafe extern "Rust" {
+        fn main() -> !;
+    }
+
+    unsafe fn zero(mut dst: *mut u32, end: *mut u32) {
+        while dst < end {
+            unsafe {
+                ::core::ptr::write_volatile(dst, 0);
+                dst = dst.add(1);
+            }
+        }
+    }
+
+    let mut record = ::core::ptr::addr_of!(__szero_table);
+    while record < ::core::ptr::addr_of!(__ezero_table) {
+        unsafe {
+            let [start, end] = *record;
+            zero(start, end);
+            record = record.add(1);
+        }
+    }
+    unsafe { main() }
+}
+
+/// The memory the linker script reserves for the heap, e.g. to initialize an allocator with.
+///
+/// # Safety
+///
+/// Must be called at most once, as every call returns the same memory.
+pub unsafe fn heap() -> &'static mut [::core::mem::MaybeUninit<u8>] {
+    unsafe extern "C" {
+        static mut __sheap: u8;
+        static mut __eheap: u8;
+    }
+
+    let start = ::core::ptr::addr_of_mut!(__sheap);
+    let end = ::core::ptr::addr_of_mut!(__eheap);
+    unsafe { ::core::slice::from_raw_parts_mut(start.cast(), end as usize - start as usize) }
+}
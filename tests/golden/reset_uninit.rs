@@ -0,0 +1,94 @@
+// Generated by ld_script. Do not edit.
+
+/// Entry point of the firmware. Initializes memory and calls `main`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn Reset() -> ! {
+    unsafe extern "C" {
+        static __szero_table: [*mut u32; 2];
+        static __ezero_table: [*mut u32; 2];
+    }
+
+    unsafe extern "Rust" {
+        fn main() -> !;
+    }
+
+    unsafe fn zero(mut dst: *mut u32, end: *mut u32) {
+        while dst < end {
+            unsafe {
+                ::core::ptr::write_volatile(dst, 0);
+                dst = dst.add(1);
+            }
+        }
+    }
+
+    let mut record = ::core::ptr::addr_of!(__szero_table);
+    while record < ::core::ptr::addr_of!(__ezero_table) {
+        unsafe {
+            let [start, end] = *record;
+            zero(start, end);
+            record = record.add(1);
+        }
+    }
+    unsafe { main() }
+}
+
+/// The magic word in the header of `.uninit`, written by `seal_uninit`.
+pub const UNINIT_MAGIC: u32 = 0x494e4f4e;
+
+// The header of `.uninit`, i.e. `UNINIT_MAGIC` and the CRC-32 of the contents, and its contents
+unsafe fn uninit_memory() -> (*mut u32, *mut u8, usize) {
+    unsafe extern "C" {
+        static mut __suninit: u32;
+        static mut __euninit: u32;
+    }
+
+    let start = ::core::ptr::addr_of_mut!(__suninit);
+    let end = ::core::ptr::addr_of_mut!(__euninit);
+    let contents = unsafe { start.add(2) }.cast::<u8>();
+    (start, contents, end as usize - contents as usize)
+}
+
+unsafe fn uninit_crc(contents: *const u8, len: usize) -> u32 {
+    let mut crc = !0u32;
+    for i in 0..len {
+        crc ^= u32::from(unsafe { ::core::ptr::read_volatile(contents.add(i)) });
+        for _ in 0..8 {
+            crc = (crc >> 1) ^ (0xedb88320 & (crc & 1).wrapping_neg());
+        }
+    }
+    !crc
+}
+
+/// The contents of `.uninit`, which `Reset` neither copies nor zeroes. They are `Ok` if they
+/// match the header written by `seal_uninit` before a warm reset, and `Err` after a cold boot, when
+/// they hold whatever the RAM powered up with.
+///
+/// # Safety
+///
+/// Must not be called while the memory returned by an earlier call is still in use.
+pub unsafe fn uninit() -> Result<&'static mut [u8], &'static mut [::core::mem::MaybeUninit<u8>]> {
+    unsafe {
+        let (header, contents, len) = uninit_memory();
+        if ::core::ptr::read_volatile(header) == UNINIT_MAGIC
+            && ::core::ptr::read_volatile(header.add(1)) == uninit_crc(contents, len)
+        {
+            Ok(::core::slice::from_raw_parts_mut(contents, len))
+        } else {
+            Err(::core::slice::from_raw_parts_mut(contents.cast(), len))
+        }
+    }
+}
+
+/// Updates the header of `.uninit` to match its contents, so that they are `Ok` after the next
+/// warm reset. Must be called after every change to them.
+///
+/// # Safety
+///
+/// Must not be called while the memory returned by `uninit` is being written.
+pub unsafe fn seal_uninit() {
+    unsafe {
+        let (header, contents, len) = uninit_memory();
+        ::core::ptr::write_volatile(header.add(1), uninit_crc(contents, len));
+        ::core::ptr::write_volatile(header, UNINIT_MAGIC);
+    }
+}
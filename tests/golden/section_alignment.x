@@ -0,0 +1,52 @@
+/* Generated by ld_script. Do not edit. */
+
+ENTRY(Reset);
+
+MEMORY
+{
+    flash (rx) : ORIGIN = 0x08000000, LENGTH = 0x00080000
+    ram (rwx) : ORIGIN = 0x20000000, LENGTH = 0x00020000
+}
+
+SECTIONS
+{
+    .vector_table ORIGIN(flash) + 0x0 : ALIGN(1024)
+    {
+        __svector_table = .;
+        KEEP(*(.vector_table .vector_table.*))
+        . = ALIGN(1024);
+        __evector_table = .;
+    } > flash
+    ASSERT(SIZEOF(.vector_table) <= 0x400, ".vector_table exceeds its budget of 1 KiB")
+
+    .text :
+    {
+        __stext = .;
+        *(.text .text.*)
+        __etext = .;
+    } > flash
+
+    .zero_table : ALIGN(4)
+    {
+        __szero_table = .;
+        LONG(__sbss);
+        LONG(__ebss);
+        __ezero_table = .;
+    } > flash
+
+    .bss (NOLOAD) : ALIGN(8)
+    {
+        __sbss = .;
+        *(.bss .bss.* COMMON)
+        . = ALIGN(8);
+        __ebss = .;
+    } > ram
+
+    .dma : ALIGN(32)
+    {
+        __sdma = .;
+        *(.dma .dma.*)
+        . = ALIGN(32);
+        __edma = .;
+    } > ram
+}
@@ -0,0 +1,109 @@
+/* Generated by ld_script. Do not edit. */
+
+ENTRY(Reset);
+
+MEMORY
+{
+    flash (rx) : ORIGIN = 0x08000000, LENGTH = 0x00080000
+    ram (rwx) : ORIGIN = 0x20000000, LENGTH = 0x00020000
+}
+
+SECTIONS
+{
+    .vector_table :
+    {
+        __svector_table = .;
+        KEEP(*(.vector_table .vector_table.*))
+        __evector_table = .;
+    } > flash
+
+    .text :
+    {
+        __stext = .;
+        *(.text .text.*)
+        __etext = .;
+    } > flash
+
+    .copy_table : ALIGN(4)
+    {
+        __scopy_table = .;
+        LONG(__siramfunc);
+        LONG(__sramfunc);
+        LONG(__eramfunc);
+        LONG(__sidata);
+        LONG(__sdata);
+        LONG(__edata);
+        LONG(__siconfig);
+        LONG(__sconfig);
+        LONG(__econfig);
+        __ecopy_table = .;
+    } > flash
+
+    .zero_table : ALIGN(4)
+    {
+        __szero_table = .;
+        LONG(__sbss);
+        LONG(__ebss);
+        __ezero_table = .;
+    } > flash
+
+    .rodata :
+    {
+        __srodata = .;
+        *(.rodata .rodata.*)
+        __erodata = .;
+    } > flash
+
+    .ramfunc : ALIGN(4)
+    {
+        __sramfunc = .;
+        *(.ramfunc .ramfunc.*)
+        . = ALIGN(4);
+        __eramfunc = .;
+    } > ram AT> flash
+    __siramfunc = LOADADDR(.ramfunc);
+
+    .data : ALIGN(4)
+    {
+        __sdata = .;
+        *(.data .data.*)
+        . = ALIGN(4);
+        __edata = .;
+    } > ram AT> flash
+    __sidata = LOADADDR(.data);
+
+    .bss (NOLOAD) : ALIGN(4)
+    {
+        __sbss = .;
+        *(.bss .bss.* COMMON)
+        . = ALIGN(4);
+        __ebss = .;
+    } > ram
+
+    .uninit (NOLOAD) : ALIGN(4)
+    {
+        __suninit = .;
+        . += 0x8;
+        *(.uninit .uninit.*)
+        . = ALIGN(4);
+        __euninit = .;
+    } > ram
+
+    .config : ALIGN(4)
+    {
+        __sconfig = .;
+        *(.config .config.*)
+        . = ALIGN(4);
+        __econfig = .;
+    } > ram AT> flash
+    __siconfig = LOADADDR(.config);
+
+    .stack ORIGIN(ram) + LENGTH(ram) - 0x1000 (NOLOAD) : ALIGN(8)
+    {
+        __sstack = .;
+        . += 0x1000;
+        __estack = .;
+    } > ram
+    _stack_start = __estack;
+    ASSERT(SIZEOF(.stack) <= 0x1000, ".stack exceeds its budget of 4 KiB")
+}
@@ -0,0 +1,77 @@
+/* Generated by ld_script. Do not edit. */
+
+ENTRY(Reset);
+
+MEMORY
+{
+    flash (rx) : ORIGIN = 0x08000000, LENGTH = 0x00080000
+    ram (rwx) : ORIGIN = 0x20000000, LENGTH = 0x00020000
+    sram4 (rw) : ORIGIN = 0x38000000, LENGTH = 0x00010000
+}
+
+SECTIONS
+{
+    .text :
+    {
+        __stext = .;
+        *(.text .text.*)
+        __etext = .;
+    } > flash
+
+    .zero_table : ALIGN(4)
+    {
+        __szero_table = .;
+        LONG(__sbss);
+        LONG(__ebss);
+        __ezero_table = .;
+    } > flash
+
+    .bss (NOLOAD) : ALIGN(4)
+    {
+        __sbss = .;
+        *(.bss .bss.* COMMON)
+        . = ALIGN(4);
+        __ebss = .;
+    } > ram
+
+    .stack_guard ORIGIN(ram) + LENGTH(ram) - 0x2a00 (NOLOAD) :
+    {
+        __sstack_guard = .;
+        . += 0x100;
+        __estack_guard = .;
+    } > ram
+
+    .stack ORIGIN(ram) + LENGTH(ram) - 0x2900 (NOLOAD) : ALIGN(8)
+    {
+        __sstack = .;
+        . += 0x800;
+        __estack = .;
+    } > ram
+    _stack_start = __estack;
+    ASSERT(SIZEOF(.stack) <= 0x800, ".stack exceeds its budget of 2 KiB")
+
+    .psp_stack_guard ORIGIN(ram) + LENGTH(ram) - 0x2100 (NOLOAD) :
+    {
+        __spsp_stack_guard = .;
+        . += 0x100;
+        __epsp_stack_guard = .;
+    } > ram
+
+    .psp_stack ORIGIN(ram) + LENGTH(ram) - 0x2000 (NOLOAD) : ALIGN(8)
+    {
+        __spsp_stack = .;
+        . += 0x2000;
+        __epsp_stack = .;
+    } > ram
+    _psp_stack_start = __epsp_stack;
+    ASSERT(SIZEOF(.psp_stack) <= 0x2000, ".psp_stack exceeds its budget of 8 KiB")
+
+    .core1_stack ORIGIN(sram4) + LENGTH(sram4) - 0x1000 (NOLOAD) : ALIGN(8)
+    {
+        __score1_stack = .;
+        . += 0x1000;
+        __ecore1_stack = .;
+    } > sram4
+    _core1_stack_start = __ecore1_stack;
+    ASSERT(SIZEOF(.core1_stack) <= 0x1000, ".core1_stack exceeds its budget of 4 KiB")
+}
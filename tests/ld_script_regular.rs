@@ -1,4 +1,4 @@
-use ld_script::{Address, MemoryLayout, U32Ext};
+use ld_script::{Address, MemoryLayout, MemoryRegion, U32Ext};
 
 #[test]
 fn create_regular_ld_script() {
@@ -82,3 +82,240 @@ fn create_regular_ld_script() {
 //         },
 //     },
 // }
+
+#[test]
+fn create_ld_script_with_non_executable_ram() {
+    let mut layout = MemoryLayout::new().unwrap();
+
+    let flash = layout
+        .add_rx_region("flash", Address::new(0x00000000), 32.kilobytes())
+        .unwrap();
+
+    let sram = layout
+        .add_rw_region("sram", Address::new(0x20000000), 64.kilobytes())
+        .unwrap();
+
+    let ram = layout
+        .add_rwx_region("ram", Address::new(0x10000000), 16.kilobytes())
+        .unwrap();
+
+    layout.text(&flash, &flash, None).unwrap();
+    layout.rodata(&flash, &flash, None).unwrap();
+    layout.data(&sram, &flash, None).unwrap();
+    layout.bss(&sram, None).unwrap();
+    layout.uninit(&sram, None).unwrap();
+    layout.stack(&sram, None).unwrap();
+    layout.ramfunc(&ram, &flash, None).unwrap();
+}
+
+#[test]
+fn sections_record_vma_and_lma() {
+    let mut layout = MemoryLayout::new().unwrap();
+
+    let flash = layout
+        .add_rx_region("flash", Address::new(0x00000000), 32.kilobytes())
+        .unwrap();
+
+    let ram = layout
+        .add_rwx_region("ram", Address::new(0x20000000), 256.kilobytes())
+        .unwrap();
+
+    layout.text(&flash, &flash, Some(16.kilobytes())).unwrap();
+    layout.data(&ram, &flash, None).unwrap();
+    layout.ramfunc(&ram, &flash, None).unwrap();
+    layout.bss(&ram, None).unwrap();
+
+    let data = layout.sections().find(|s| s.name() == "data").unwrap();
+    assert_eq!(data.vma(), ram.get_id());
+    assert_eq!(data.lma(), flash.get_id());
+    assert!(data.size().is_none());
+
+    let ramfunc = layout.sections().find(|s| s.name() == "ramfunc").unwrap();
+    assert_eq!(ramfunc.vma(), ram.get_id());
+    assert_eq!(ramfunc.lma(), flash.get_id());
+
+    let text = layout.sections().find(|s| s.name() == "text").unwrap();
+    assert_eq!(text.vma(), flash.get_id());
+    assert_eq!(text.lma(), flash.get_id());
+    assert!(text.size().is_some());
+
+    let bss = layout.sections().find(|s| s.name() == "bss").unwrap();
+    assert_eq!(bss.vma(), ram.get_id());
+    assert_eq!(bss.lma(), ram.get_id());
+
+    let names: Vec<_> = layout.sections().map(|s| s.name()).collect();
+    assert_eq!(names, ["text", "ramfunc", "data", "bss"]);
+}
+
+#[test]
+fn sections_follow_conventional_order() {
+    let mut layout = MemoryLayout::new().unwrap();
+
+    let flash = layout
+        .add_rx_region("flash", Address::new(0x00000000), 32.kilobytes())
+        .unwrap();
+
+    let ram = layout
+        .add_rwx_region("ram", Address::new(0x20000000), 256.kilobytes())
+        .unwrap();
+
+    layout.custom_section("logs", &ram, &ram, None).unwrap();
+    layout.stack(&ram, None).unwrap();
+    layout.uninit(&ram, None).unwrap();
+    layout
+        .custom_section("config", &flash, &flash, None)
+        .unwrap();
+    layout.bss(&ram, None).unwrap();
+    layout.data(&ram, &flash, None).unwrap();
+    layout.rodata(&flash, &flash, None).unwrap();
+    layout.text(&flash, &flash, None).unwrap();
+    layout.vector_table(&flash, &flash, None).unwrap();
+
+    let names: Vec<_> = layout.sections().map(|s| s.name()).collect();
+    assert_eq!(
+        names,
+        [
+            "vector_table",
+            "text",
+            "rodata",
+            "data",
+            "bss",
+            "uninit",
+            "logs",
+            "config",
+            "stack"
+        ]
+    );
+}
+
+#[test]
+fn custom_sections_next_to_other_sections() {
+    let mut layout = MemoryLayout::new().unwrap();
+
+    let flash = layout
+        .add_rx_region("flash", Address::new(0x00000000), 32.kilobytes())
+        .unwrap();
+
+    let ram = layout
+        .add_rwx_region("ram", Address::new(0x20000000), 256.kilobytes())
+        .unwrap();
+
+    layout.vector_table(&flash, &flash, None).unwrap();
+    layout.bss(&ram, None).unwrap();
+    layout
+        .custom_section_after("header", "vector_table", &flash, &flash, None)
+        .unwrap();
+    layout
+        .custom_section_before("dma", "bss", &ram, &ram, None)
+        .unwrap();
+    layout.text(&flash, &flash, None).unwrap();
+    layout.data(&ram, &flash, None).unwrap();
+
+    let names: Vec<_> = layout.sections().map(|s| s.name()).collect();
+    assert_eq!(
+        names,
+        ["vector_table", "header", "text", "data", "dma", "bss"]
+    );
+
+    assert!(layout
+        .custom_section_after("trailer", "heap", &ram, &ram, None)
+        .is_err());
+}
+
+#[test]
+fn readding_a_section_keeps_its_settings() {
+    let mut layout = MemoryLayout::new().unwrap();
+
+    let flash = layout
+        .add_rx_region("flash", Address::new(0x00000000), 32.kilobytes())
+        .unwrap();
+
+    let ram = layout
+        .add_rwx_region("ram", Address::new(0x20000000), 256.kilobytes())
+        .unwrap();
+
+    layout.text(&flash, &flash, None).unwrap();
+    layout.set_section_offset("text", 0x400.bytes()).unwrap();
+    layout.set_section_alignment("text", 16.bytes()).unwrap();
+    layout.stack(&ram, None).unwrap();
+    layout.set_stack_guard("stack", 256.bytes()).unwrap();
+    layout.set_stack_painting("stack", true).unwrap();
+
+    layout.text(&flash, &flash, Some(16.kilobytes())).unwrap();
+    layout.stack(&ram, Some(4.kilobytes())).unwrap();
+
+    let text = layout.section("text").unwrap();
+    assert_eq!(text.size(), Some(16.kilobytes()));
+    assert_eq!(text.offset(), Some(0x400.bytes()));
+    assert_eq!(text.alignment(), 16.bytes());
+    let stack = layout.section("stack").unwrap();
+    assert_eq!(stack.guard(), Some(256.bytes()));
+    assert!(stack.is_painted());
+}
+
+#[test]
+fn layout_can_be_inspected() {
+    let mut layout = MemoryLayout::new().unwrap();
+
+    let flash = layout
+        .add_rx_region("flash", Address::new(0x00000000), 32.kilobytes())
+        .unwrap();
+
+    let ram = layout
+        .add_rwx_region("ram", Address::new(0x20000000), 256.kilobytes())
+        .unwrap();
+
+    layout.vector_table(&flash, &flash, None).unwrap();
+    layout.text(&flash, &flash, Some(16.kilobytes())).unwrap();
+    layout.data(&ram, &flash, None).unwrap();
+    layout.stack(&ram, Some(4.kilobytes())).unwrap();
+    layout
+        .set_section_offset("vector_table", 0.bytes())
+        .unwrap();
+
+    let regions: Vec<_> = layout
+        .regions()
+        .map(|region| {
+            (
+                region.get_id().to_string(),
+                region.get_base_addres(),
+                region.get_size(),
+                region.get_attributes(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        regions,
+        [
+            (
+                "flash".to_owned(),
+                Address::new(0x00000000),
+                32.kilobytes(),
+                "rx"
+            ),
+            (
+                "ram".to_owned(),
+                Address::new(0x20000000),
+                256.kilobytes(),
+                "rwx"
+            ),
+        ]
+    );
+
+    let region = layout.region(ram.get_id()).unwrap();
+    assert_eq!(region.get_base_addres(), Address::new(0x20000000));
+
+    let vector_table = layout.section("vector_table").unwrap();
+    assert_eq!(vector_table.offset(), Some(0.bytes()));
+    assert_eq!(vector_table.alignment(), 1.bytes());
+
+    let data = layout.section("data").unwrap();
+    assert_eq!(data.offset(), None);
+    assert_eq!(data.alignment(), 4.bytes());
+
+    let stack = layout.section("stack").unwrap();
+    assert_eq!(stack.size(), Some(4.kilobytes()));
+    assert_eq!(stack.alignment(), 8.bytes());
+
+    assert!(layout.section("bss").is_none());
+}
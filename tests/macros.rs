@@ -0,0 +1,347 @@
+use ld_script::U32Ext;
+use ld_script_macros::{define_linker_script, ramfunc};
+
+mod common;
+
+use common::output_dir;
+
+define_linker_script! {
+    BudgetLinkerScript,
+    MemoryRegions => {
+        Flash => {
+            address = 0x08000000,
+            size = 512.kilobytes(),
+            access = "RX",
+        },
+        Ram => {
+            address = 0x20000000,
+            size = 128.kilobytes(),
+            access = "RWX",
+        },
+    },
+
+    Sections => {
+        Text => {
+            region = Flash,
+            size = 256.kilobytes(),
+        },
+
+        Data => {
+            vma = Ram,
+            lma = Flash,
+            size = 16.kilobytes(),
+        },
+    },
+}
+
+define_linker_script! {
+    OffsetLinkerScript,
+    MemoryRegions => {
+        Flash => {
+            address = 0x08000000,
+            size = 512.kilobytes(),
+            access = "RX",
+        },
+    },
+
+    Sections => {
+        Text => {
+            region = Flash,
+            offset = 0x400,
+        },
+
+        VectorTable => {
+            region = Flash,
+            offset = 0x000,
+            size = 0x200.bytes(),
+        },
+    },
+}
+
+define_linker_script! {
+    AlignedLinkerScript,
+    MemoryRegions => {
+        Flash => {
+            address = 0x08000000,
+            size = 512.kilobytes(),
+            access = "RX",
+        },
+        Ram => {
+            address = 0x20000000,
+            size = 128.kilobytes(),
+            access = "RW",
+        },
+    },
+
+    Sections => {
+        VectorTable => {
+            region = Flash,
+            offset = 0x000,
+            size = 0x200.bytes(),
+            align = 0x200,
+        },
+
+        Bss => {
+            region = Ram,
+            align = 32,
+        },
+    },
+}
+
+define_linker_script! {
+    HeapLinkerScript,
+    MemoryRegions => {
+        Flash => {
+            address = 0x08000000,
+            size = 512.kilobytes(),
+            access = "RX",
+        },
+        Ram => {
+            address = 0x20000000,
+            size = 128.kilobytes(),
+            access = "RW",
+        },
+    },
+
+    Sections => {
+        Bss => {
+            region = Ram,
+        },
+
+        Heap => {
+            region = Ram,
+            size = 16.kilobytes(),
+        },
+    },
+}
+
+define_linker_script! {
+    PaintedLinkerScript,
+    MemoryRegions => {
+        Flash => {
+            address = 0x08000000,
+            size = 512.kilobytes(),
+            access = "RX",
+        },
+        Ram => {
+            address = 0x20000000,
+            size = 128.kilobytes(),
+            access = "RW",
+        },
+    },
+
+    Sections => {
+        Text => {
+            region = Flash,
+        },
+
+        Stack => {
+            region = Ram,
+            size = 4.kilobytes(),
+            paint = true,
+        },
+
+        Stack => {
+            region = Ram,
+            size = 2.kilobytes(),
+            name = "psp_stack",
+            paint = false,
+        },
+    },
+}
+
+define_linker_script! {
+    UninitLinkerScript,
+    MemoryRegions => {
+        Ram => {
+            address = 0x20000000,
+            size = 128.kilobytes(),
+            access = "RW",
+        },
+    },
+
+    Sections => {
+        Bss => {
+            region = Ram,
+        },
+
+        Uninit => {
+            region = Ram,
+            size = 1.kilobytes(),
+        },
+    },
+}
+
+define_linker_script! {
+    CcramLinkerScript,
+    MemoryRegions => {
+        Flash => {
+            address = 0x08000000,
+            size = 512.kilobytes(),
+            access = "RX",
+        },
+        Ccram => {
+            address = 0x10000000,
+            size = 64.kilobytes(),
+            access = "RW",
+        },
+    },
+
+    Sections => {
+        Text => {
+            region = Flash,
+        },
+
+        CcramData => {
+            vma = Ccram,
+            lma = Flash,
+        },
+
+        CcramBss => {
+            region = Ccram,
+        },
+    },
+}
+
+define_linker_script! {
+    GuardedStacksLinkerScript,
+    MemoryRegions => {
+        Flash => {
+            address = 0x08000000,
+            size = 512.kilobytes(),
+            access = "RX",
+        },
+        Ram => {
+            address = 0x20000000,
+            size = 128.kilobytes(),
+            access = "RW",
+        },
+    },
+
+    Sections => {
+        Text => {
+            region = Flash,
+        },
+
+        Stack => {
+            region = Ram,
+            size = 4.kilobytes(),
+            guard = 256.bytes(),
+        },
+
+        Stack => {
+            region = Ram,
+            size = 2.kilobytes(),
+            name = "core1_stack",
+            guard = 128.bytes(),
+        },
+    },
+}
+
+#[ramfunc]
+fn checksum_in_ram(words: &[u32]) -> u32 {
+    words.iter().fold(0, |sum, word| sum.wrapping_add(*word))
+}
+
+#[test]
+fn section_budgets_are_asserted() {
+    let path = output_dir("macro_section_budgets_are_asserted");
+    BudgetLinkerScript::new(&path).generate().unwrap();
+
+    assert_eq!(
+        std::fs::read_to_string(path.join("link.x")).unwrap(),
+        include_str!("golden/macro_section_budgets.x")
+    );
+}
+
+#[test]
+fn sections_at_fixed_offsets() {
+    let path = output_dir("macro_sections_at_fixed_offsets");
+    OffsetLinkerScript::new(&path).generate().unwrap();
+
+    assert_eq!(
+        std::fs::read_to_string(path.join("link.x")).unwrap(),
+        include_str!("golden/macro_section_offsets.x")
+    );
+
+    let sections = common::link(&path, &["vector_table"]);
+    assert_eq!(sections[".vector_table"], (0x08000000, 0x08000000));
+    assert_eq!(sections[".text"], (0x08000400, 0x08000400));
+}
+
+#[test]
+fn sections_are_aligned() {
+    let path = output_dir("macro_sections_are_aligned");
+    AlignedLinkerScript::new(&path).generate().unwrap();
+
+    assert_eq!(
+        std::fs::read_to_string(path.join("link.x")).unwrap(),
+        include_str!("golden/macro_section_alignment.x")
+    );
+}
+
+#[test]
+fn heap_has_a_fixed_size() {
+    let path = output_dir("macro_heap_has_a_fixed_size");
+    HeapLinkerScript::new(&path).generate().unwrap();
+
+    assert_eq!(
+        std::fs::read_to_string(path.join("link.x")).unwrap(),
+        include_str!("golden/macro_heap.x")
+    );
+}
+
+#[test]
+fn ccram_sections_are_initialized() {
+    let path = output_dir("macro_ccram_sections_are_initialized");
+    CcramLinkerScript::new(&path).generate().unwrap();
+
+    assert_eq!(
+        std::fs::read_to_string(path.join("link.x")).unwrap(),
+        include_str!("golden/macro_ccram.x")
+    );
+}
+
+#[test]
+fn uninit_is_placed() {
+    let path = output_dir("macro_uninit_is_placed");
+    UninitLinkerScript::new(&path).generate().unwrap();
+
+    assert_eq!(
+        std::fs::read_to_string(path.join("link.x")).unwrap(),
+        include_str!("golden/macro_uninit.x")
+    );
+}
+
+#[test]
+fn stack_painting_is_forwarded() {
+    let path = output_dir("macro_stack_painting_is_forwarded");
+    PaintedLinkerScript::new(&path).generate_reset().unwrap();
+
+    assert_eq!(
+        std::fs::read_to_string(path.join("reset.rs")).unwrap(),
+        include_str!("golden/macro_reset_painted.rs")
+    );
+}
+
+#[test]
+fn ramfunc_is_callable() {
+    assert_eq!(checksum_in_ram(&[1, 2, u32::MAX]), 2);
+}
+
+#[test]
+fn stacks_are_named_and_guarded() {
+    let path = output_dir("macro_stacks_are_named_and_guarded");
+    GuardedStacksLinkerScript::new(&path).generate().unwrap();
+
+    assert_eq!(
+        std::fs::read_to_string(path.join("link.x")).unwrap(),
+        include_str!("golden/macro_stacks_guarded.x")
+    );
+
+    let sections = common::link(&path, &[]);
+    assert_eq!(sections[".stack_guard"], (0x2001e680, 0x2001e680));
+    assert_eq!(sections[".stack"], (0x2001e780, 0x2001e780));
+    assert_eq!(sections[".core1_stack_guard"], (0x2001f780, 0x2001f780));
+    assert_eq!(sections[".core1_stack"], (0x2001f800, 0x2001f800));
+}
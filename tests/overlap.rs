@@ -0,0 +1,90 @@
+use ld_script::{
+    Address, AddressWidth, Error, Memory, MemoryLayout, MemoryRegion, Size, U32Ext, RWX,
+};
+use proptest::prelude::*;
+
+fn intersects(a: (u32, u32), b: (u32, u32)) -> bool {
+    let (a_start, a_end) = (u64::from(a.0), u64::from(a.0) + u64::from(a.1));
+    let (b_start, b_end) = (u64::from(b.0), u64::from(b.0) + u64::from(b.1));
+    a_start.max(b_start) < a_end.min(b_end)
+}
+
+// Regions are drawn from a small address space so that collisions are frequent
+fn region() -> impl Strategy<Value = (u32, u32)> {
+    (0u32..0x1000, 1u32..0x400)
+}
+
+proptest! {
+    #[test]
+    fn second_region_is_rejected_iff_it_intersects(first in region(), second in region()) {
+        let mut layout = MemoryLayout::new().unwrap();
+        let existing = layout
+            .add_rwx_region("first", Address::new(first.0.into()), first.1.bytes())
+            .unwrap();
+
+        match layout.add_rwx_region("second", Address::new(second.0.into()), second.1.bytes()) {
+            Ok(_) => prop_assert!(!intersects(first, second)),
+            Err(Error::OverlapingMemoryRegion { existing: id, .. }) => {
+                prop_assert!(intersects(first, second));
+                prop_assert_eq!(&id, existing.get_id());
+            }
+            Err(error) => prop_assert!(false, "unexpected error {:?}", error),
+        }
+    }
+
+    #[test]
+    fn accepted_regions_are_disjoint(regions in prop::collection::vec(region(), 1..16)) {
+        let mut layout = MemoryLayout::new().unwrap();
+        let mut accepted: Vec<((u32, u32), Memory<RWX>)> = vec![];
+
+        for (index, &region) in regions.iter().enumerate() {
+            let name = format!("region{}", index);
+            match layout.add_rwx_region(&name, Address::new(region.0.into()), region.1.bytes()) {
+                Ok(memory) => {
+                    prop_assert!(accepted.iter().all(|(other, _)| !intersects(region, *other)));
+                    accepted.push((region, memory));
+                }
+                Err(Error::OverlapingMemoryRegion { existing: id, .. }) => {
+                    // The reported region is the first accepted one that intersects
+                    let (_, first) = accepted
+                        .iter()
+                        .find(|(other, _)| intersects(region, *other))
+                        .unwrap();
+                    prop_assert_eq!(&id, first.get_id());
+                }
+                Err(error) => prop_assert!(false, "unexpected error {:?}", error),
+            }
+        }
+    }
+
+    #[test]
+    fn regions_must_fit_in_address_space(base in any::<u32>(), size in 1u32..) {
+        let mut layout = MemoryLayout::new().unwrap();
+        let fits = u64::from(base) + u64::from(size) <= 1 << 32;
+
+        match layout.add_rwx_region("region", Address::new(base.into()), size.bytes()) {
+            Ok(_) => prop_assert!(fits),
+            Err(Error::AddressWrapAround(_)) => prop_assert!(!fits),
+            Err(error) => prop_assert!(false, "unexpected error {:?}", error),
+        }
+    }
+
+    #[test]
+    fn regions_must_fit_in_64_bit_address_space(base in any::<u64>(), size in 1u64..) {
+        let mut layout = MemoryLayout::with_address_width(AddressWidth::Bits64).unwrap();
+        let fits = u128::from(base) + u128::from(size) <= 1 << 64;
+
+        match layout.add_rwx_region("region", Address::new(base), Size::new(size)) {
+            Ok(_) => prop_assert!(fits),
+            Err(Error::AddressWrapAround(_)) => prop_assert!(!fits),
+            Err(error) => prop_assert!(false, "unexpected error {:?}", error),
+        }
+    }
+
+    #[test]
+    fn zero_sized_regions_are_rejected(base in any::<u32>()) {
+        let mut layout = MemoryLayout::new().unwrap();
+        let result = layout.add_rwx_region("region", Address::new(base.into()), 0.bytes());
+        prop_assert!(matches!(result, Err(Error::ZeroSizedRegion(_))));
+    }
+}
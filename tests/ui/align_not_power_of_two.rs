@@ -0,0 +1,21 @@
+use ld_script_macros::define_linker_script;
+
+define_linker_script! {
+    LinkerScript,
+    MemoryRegions => {
+        Flash => {
+            address = 0x08000000,
+            size = 0x1000.bytes(),
+            access = "RX",
+        },
+    },
+
+    Sections => {
+        VectorTable => {
+            region = Flash,
+            align = 0x300,
+        },
+    },
+}
+
+fn main() {}
@@ -0,0 +1,21 @@
+use ld_script::U32Ext;
+use ld_script_macros::define_linker_script;
+
+define_linker_script! {
+    LinkerScript,
+    MemoryRegions => {
+        Flash => {
+            address = 0x08000000,
+            size = 512.kilobytes(),
+            access = "RX",
+        },
+    },
+
+    Sections => {
+        Bss => {
+            region = Flash,
+        },
+    },
+}
+
+fn main() {}
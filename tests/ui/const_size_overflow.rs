@@ -0,0 +1,7 @@
+use ld_script::Size;
+
+const EXTERNAL_RAM: Size = Size::gib(1 << 34);
+
+fn main() {
+    let _ = EXTERNAL_RAM;
+}
@@ -0,0 +1,25 @@
+use ld_script_macros::define_linker_script;
+
+define_linker_script! {
+    LinkerScript,
+    MemoryRegions => {
+        Flash => {
+            address = 0x08000000,
+            size = 0x1000.bytes(),
+            access = "RX",
+        },
+        FLASH => {
+            address = 0x10000000,
+            size = 0x1000.bytes(),
+            access = "RX",
+        },
+    },
+
+    Sections => {
+        Text => {
+            region = Flash,
+        },
+    },
+}
+
+fn main() {}
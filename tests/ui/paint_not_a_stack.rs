@@ -0,0 +1,22 @@
+use ld_script::U32Ext;
+use ld_script_macros::define_linker_script;
+
+define_linker_script! {
+    LinkerScript,
+    MemoryRegions => {
+        Ram => {
+            address = 0x20000000,
+            size = 0x1000.bytes(),
+            access = "RW",
+        },
+    },
+
+    Sections => {
+        Bss => {
+            region = Ram,
+            paint = true,
+        },
+    },
+}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use ld_script_macros::ramfunc;
+
+#[ramfunc]
+static BUFFER: [u8; 16] = [0; 16];
+
+fn main() {}
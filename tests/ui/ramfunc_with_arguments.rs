@@ -0,0 +1,6 @@
+use ld_script_macros::ramfunc;
+
+#[ramfunc(itcm)]
+fn program_flash() {}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use ld_script::{Address, MemoryLayout, U32Ext};
+
+fn main() {
+    let mut layout = MemoryLayout::new().unwrap();
+    let flash = layout
+        .add_rx_region("flash", Address::new(0x08000000), 512.kilobytes())
+        .unwrap();
+    let sram = layout
+        .add_rw_region("sram", Address::new(0x20000000), 128.kilobytes())
+        .unwrap();
+
+    layout.uninit(&flash, None).unwrap();
+}
@@ -0,0 +1,20 @@
+use ld_script_macros::define_linker_script;
+
+define_linker_script! {
+    LinkerScript,
+    MemoryRegions => {
+        Flash => {
+            address = 0x08000000,
+            size = 512.kilobytes(),
+            access = "RO",
+        },
+    },
+
+    Sections => {
+        Text => {
+            region = Flash,
+        },
+    },
+}
+
+fn main() {}